@@ -0,0 +1,692 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! A local archive of Redditors' activity.
+//!
+//! Reddit only returns a user's most recent comments and submissions, so
+//! anything older eventually scrolls out of reach. An [`Archive`] keeps
+//! every comment, submission, and account snapshot that `usaidwat` has ever
+//! fetched in a SQLite database (usually
+//! `~/.local/share/usaidwat/archive.sqlite3`), keyed by the thing's
+//! [fullname], so a user's accumulated history can be analyzed later.
+//!
+//! Wrap a service in an [`ArchivingService`] to archive everything it
+//! fetches. An `Archive` is itself a [`Service`] that serves a user's
+//! archived history as if it had come from Reddit.
+//!
+//! [fullname]: https://www.reddit.com/dev/api/#fullnames
+
+use crate::reddit::resource::{ListingParams, Resource, Sort};
+use crate::reddit::service::Service;
+use anyhow::{Context, anyhow};
+use hypertyper::prelude::*;
+use log::{debug, warn};
+use reqwest::StatusCode;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS things (
+        fullname    TEXT PRIMARY KEY,
+        kind        TEXT NOT NULL,
+        author      TEXT NOT NULL COLLATE NOCASE,
+        created_utc REAL NOT NULL,
+        data        TEXT NOT NULL,
+        first_seen  INTEGER NOT NULL,
+        last_seen   INTEGER NOT NULL,
+        removed_at  INTEGER
+    );
+    CREATE INDEX IF NOT EXISTS things_by_author ON things (author, kind, created_utc);
+";
+
+/// Reddit's kind prefix for accounts.
+const ACCOUNT: &str = "t2";
+
+/// Reddit's kind prefix for comments.
+const COMMENT: &str = "t1";
+
+/// Reddit's kind prefix for submissions (links).
+const SUBMISSION: &str = "t3";
+
+/// A SQLite database of archived Reddit things.
+#[derive(Debug)]
+pub struct Archive {
+    conn: Mutex<Connection>,
+    exclude_removed: bool,
+}
+
+impl Archive {
+    /// Opens the archive at `path`, creating it if necessary.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("could not create {}", dir.display()))?;
+        }
+        let conn = Connection::open(path)
+            .with_context(|| format!("could not open archive {}", path.display()))?;
+        Self::with_connection(conn)
+    }
+
+    /// Opens the archive in its default location, which is
+    /// `archive.sqlite3` in the `usaidwat` subdirectory of the user's data
+    /// directory.
+    pub fn open_default() -> anyhow::Result<Self> {
+        let dir = dirs::data_dir().ok_or_else(|| anyhow!("could not find data directory"))?;
+        Self::open(dir.join("usaidwat").join("archive.sqlite3"))
+    }
+
+    /// Opens a temporary archive that only exists in memory.
+    pub fn in_memory() -> anyhow::Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("could not create archive schema")?;
+        // Archives created before removals were tracked lack the column.
+        if conn
+            .prepare("SELECT removed_at FROM things LIMIT 0")
+            .is_err()
+        {
+            conn.execute_batch("ALTER TABLE things ADD COLUMN removed_at INTEGER")
+                .context("could not update archive schema")?;
+        }
+        Ok(Self {
+            conn: Mutex::new(conn),
+            exclude_removed: false,
+        })
+    }
+
+    /// Leaves things that have been [marked as removed](Self::mark_removed)
+    /// out of the listings the archive serves.
+    pub fn exclude_removed(self) -> Self {
+        Self {
+            exclude_removed: true,
+            ..self
+        }
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().expect("archive lock is poisoned")
+    }
+
+    /// Archives the things in a raw API response for `username`'s
+    /// `resource`, returning the number of things archived.
+    ///
+    /// `data` is the JSON returned by Reddit for the user's profile,
+    /// comments, or posts. Things that are already in the archive are
+    /// replaced with the newer version, and are no longer considered
+    /// removed if they had been marked as such, unless Reddit now returns
+    /// them deleted or removed: the archived version is
+    /// the only copy of what they said, so it is kept. Other resources, like
+    /// things the user has saved, are not the user's own activity, so they
+    /// are not archived.
+    pub fn store(&self, username: &str, resource: &Resource, data: &str) -> anyhow::Result<usize> {
+        let value: Value = serde_json::from_str(data)?;
+        let things: Vec<(&str, &Value)> = match resource {
+            Resource::About => vec![(ACCOUNT, &value["data"])],
+            Resource::Comments(_) | Resource::Submitted(_) => value["data"]["children"]
+                .as_array()
+                .map(|children| {
+                    children
+                        .iter()
+                        .filter_map(|child| Some((child["kind"].as_str()?, &child["data"])))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => return Ok(0),
+        };
+
+        let now = now();
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        for (kind, thing) in things {
+            let Some(fullname) = fullname(kind, thing) else {
+                continue;
+            };
+            // Deleted things no longer name their author.
+            let author = thing["author"]
+                .as_str()
+                .filter(|author| *author != "[deleted]")
+                .or(thing["name"].as_str().filter(|_| kind == ACCOUNT))
+                .unwrap_or(username);
+            let created_utc = thing["created_utc"].as_f64().unwrap_or_default();
+            tx.execute(
+                "INSERT INTO things (fullname, kind, author, created_utc, data, first_seen, last_seen)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                 ON CONFLICT (fullname) DO UPDATE SET
+                     author = CASE WHEN ?7 THEN author ELSE excluded.author END,
+                     data = CASE WHEN ?7 THEN data ELSE excluded.data END,
+                     last_seen = excluded.last_seen,
+                     removed_at = CASE WHEN ?7 THEN removed_at ELSE NULL END",
+                params![
+                    fullname,
+                    kind,
+                    author,
+                    created_utc,
+                    thing.to_string(),
+                    now,
+                    is_placeholder(kind, thing),
+                ],
+            )?;
+            count += 1;
+        }
+        tx.commit()?;
+
+        debug!("Archived {count} things from {username}'s {resource}");
+        Ok(count)
+    }
+
+    /// Records that the things with the given `fullnames` are no longer
+    /// returned by Reddit, returning the number of things marked.
+    ///
+    /// Removed things stay in the archive, but archives that
+    /// [exclude removed things](Self::exclude_removed) no longer serve them.
+    /// Things that were already marked keep their original removal time.
+    pub fn mark_removed<'a>(
+        &self,
+        fullnames: impl IntoIterator<Item = &'a str>,
+    ) -> anyhow::Result<usize> {
+        let now = now();
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let mut count = 0;
+        for fullname in fullnames {
+            count += tx.execute(
+                "UPDATE things SET removed_at = ?1 WHERE fullname = ?2 AND removed_at IS NULL",
+                params![now, fullname],
+            )?;
+        }
+        tx.commit()?;
+
+        debug!("Marked {count} archived things as removed");
+        Ok(count)
+    }
+
+    /// The latest archived account data for `username`, in the form
+    /// returned by Reddit's `about` resource.
+    pub fn about(&self, username: &str) -> anyhow::Result<Option<String>> {
+        let data: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT data FROM things WHERE kind = ?1 AND author = ?2
+                 ORDER BY last_seen DESC LIMIT 1",
+                params![ACCOUNT, username],
+                |row| row.get(0),
+            )
+            .optional()?;
+        data.map(|data| {
+            let data: Value = serde_json::from_str(&data)?;
+            Ok(json!({ "kind": ACCOUNT, "data": data }).to_string())
+        })
+        .transpose()
+    }
+
+    /// All of `username`'s archived things of the given `kind`, in the form
+    /// of a single page of a Reddit listing.
+    ///
+    /// Things are sorted and limited to a time window according to `params`,
+    /// and removed things are left out if the archive excludes them.
+    /// Reddit's "hot" and "controversial" orders depend on voting data that
+    /// is not archived, so those listings are sorted newest first instead.
+    fn listing(
+        &self,
+        username: &str,
+        kind: &str,
+        params: &ListingParams,
+    ) -> anyhow::Result<String> {
+        let order = match params.sort_order() {
+            Some(Sort::Top) => "json_extract(data, '$.score') DESC, created_utc DESC",
+            Some(sort @ (Sort::Hot | Sort::Controversial)) => {
+                warn!("Archived listings cannot be sorted by {sort}; sorting by new instead");
+                "created_utc DESC"
+            }
+            Some(Sort::New) | None => "created_utc DESC",
+        };
+        let since = params
+            .time_window()
+            .and_then(|time| time.duration())
+            .and_then(|duration| SystemTime::now().checked_sub(duration))
+            .and_then(|since| since.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_secs_f64())
+            .unwrap_or_default();
+
+        let removed = if self.exclude_removed {
+            "AND removed_at IS NULL"
+        } else {
+            ""
+        };
+
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT data FROM things WHERE kind = ?1 AND author = ?2 AND created_utc >= ?3 {removed}
+             ORDER BY {order}"
+        ))?;
+        let children = stmt
+            .query_map(params![kind, username, since], |row| {
+                row.get::<_, String>(0)
+            })?
+            .map(|data| {
+                let data: Value = serde_json::from_str(&data?)?;
+                Ok(json!({ "kind": kind, "data": data }))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(json!({
+            "kind": "Listing",
+            "data": { "after": null, "children": children },
+        })
+        .to_string())
+    }
+}
+
+/// The current time, in seconds since the Unix epoch.
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// True if a `thing` of the given `kind` is only Reddit's placeholder for
+/// something that has been deleted or removed.
+fn is_placeholder(kind: &str, thing: &Value) -> bool {
+    let is_placeholder_text = |text: &Value| {
+        matches!(
+            text.as_str().map(str::trim),
+            Some("[deleted]" | "[removed]")
+        )
+    };
+    match kind {
+        COMMENT => is_placeholder_text(&thing["body"]),
+        SUBMISSION => {
+            !thing["removed_by_category"].is_null() || is_placeholder_text(&thing["selftext"])
+        }
+        _ => false,
+    }
+}
+
+/// The fullname of a `thing` of the given `kind`, which is its `name` field,
+/// or, for accounts, the account's `id` prefixed by its kind.
+fn fullname(kind: &str, thing: &Value) -> Option<String> {
+    if kind == ACCOUNT {
+        thing["id"].as_str().map(|id| format!("{ACCOUNT}_{id}"))
+    } else {
+        thing["name"].as_str().map(String::from)
+    }
+}
+
+impl HttpGet for Archive {
+    /// Archives cannot retrieve arbitrary URIs, so this always fails.
+    async fn get<U>(&self, _uri: U) -> HttpResult<String>
+    where
+        U: IntoUrl + Send,
+    {
+        Err(HttpError::Http(StatusCode::NOT_FOUND))
+    }
+}
+
+impl Service for Archive {
+    /// Serves `username`'s archived history for `resource`.
+    ///
+    /// The entire history is returned as the first page of a listing, so
+    /// requests for later pages return nothing.
+    async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+        let result = match resource {
+            Resource::About => self.about(username).transpose(),
+            Resource::Comments(params) | Resource::Submitted(params)
+                if params.cursor().is_some() =>
+            {
+                Some(Ok(json!({
+                    "kind": "Listing",
+                    "data": { "after": null, "children": [] },
+                })
+                .to_string()))
+            }
+            Resource::Comments(params) => Some(self.listing(username, COMMENT, params)),
+            Resource::Submitted(params) => Some(self.listing(username, SUBMISSION, params)),
+            _ => None,
+        };
+
+        match result {
+            Some(Ok(data)) => Ok(data),
+            Some(Err(err)) => {
+                warn!("Could not read {username}'s {resource} from archive: {err:#}");
+                Err(HttpError::Http(StatusCode::INTERNAL_SERVER_ERROR))
+            }
+            None => Err(HttpError::Http(StatusCode::NOT_FOUND)),
+        }
+    }
+
+    /// Archived history may be incomplete, so it says nothing about
+    /// whether a user is hiding their activity.
+    fn is_live(&self) -> bool {
+        false
+    }
+}
+
+/// A service that archives everything fetched by another service.
+#[derive(Debug)]
+pub struct ArchivingService<S: Service> {
+    service: S,
+    archive: Archive,
+}
+
+impl<S: Service> ArchivingService<S> {
+    /// Creates a service that archives everything `service` fetches in
+    /// `archive`.
+    pub fn new(service: S, archive: Archive) -> Self {
+        Self { service, archive }
+    }
+}
+
+impl<S: Service + Sync> HttpGet for ArchivingService<S> {
+    /// Sends a GET request using the underlying service, without archiving.
+    async fn get<U>(&self, uri: U) -> HttpResult<String>
+    where
+        U: IntoUrl + Send,
+    {
+        self.service.get(uri).await
+    }
+}
+
+impl<S: Service + Sync> Service for ArchivingService<S> {
+    async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+        let data = self.service.get_resource(username, resource).await?;
+        if let Err(err) = self.archive.store(username, resource, &data) {
+            warn!("Could not archive {username}'s {resource}: {err:#}");
+        }
+        Ok(data)
+    }
+
+    fn is_live(&self) -> bool {
+        self.service.is_live()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reddit::Redditor;
+    use crate::reddit::client::ListingOptions;
+    use crate::reddit::resource::TimeWindow;
+    use crate::reddit::thing::{Comment, HasBody};
+    use crate::test_utils::{TestService, load_data, load_data_with};
+    use std::env;
+    use uuid::Uuid;
+
+    fn archive() -> Archive {
+        let archive = Archive::in_memory().unwrap();
+        for resource in [
+            Resource::About,
+            Resource::Comments(ListingParams::default()),
+            Resource::Submitted(ListingParams::default()),
+        ] {
+            let data = load_data(&format!("{resource}_mipadi"));
+            archive.store("mipadi", &resource, &data).unwrap();
+        }
+        archive
+    }
+
+    fn count(archive: &Archive, username: &str, kind: &str) -> usize {
+        archive
+            .conn()
+            .query_row(
+                "SELECT COUNT(*) FROM things WHERE kind = ?1 AND author = ?2",
+                params![kind, username],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn options() -> ListingOptions {
+        ListingOptions::default()
+            .max_items(usize::MAX)
+            .progress(false)
+    }
+
+    #[test]
+    fn it_archives_comments_and_submissions() {
+        let archive = archive();
+        assert_eq!(count(&archive, "mipadi", COMMENT), 100);
+        assert_eq!(count(&archive, "mipadi", SUBMISSION), 100);
+    }
+
+    #[test]
+    fn it_archives_account_data() {
+        let archive = archive();
+        assert_eq!(count(&archive, "mipadi", ACCOUNT), 1);
+        assert!(archive.about("mipadi").unwrap().is_some());
+    }
+
+    #[test]
+    fn it_does_not_duplicate_things() {
+        let archive = archive();
+        let data = load_data("comments_mipadi");
+        archive
+            .store(
+                "mipadi",
+                &Resource::Comments(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        assert_eq!(count(&archive, "mipadi", COMMENT), 100);
+    }
+
+    #[test]
+    fn it_keeps_things_that_are_no_longer_returned() {
+        let archive = archive();
+        let data = load_data("comments_empty");
+        archive
+            .store(
+                "mipadi",
+                &Resource::Comments(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        assert_eq!(count(&archive, "mipadi", COMMENT), 100);
+    }
+
+    #[test]
+    fn it_does_not_archive_other_users_things() {
+        let archive = Archive::in_memory().unwrap();
+        let data = load_data("comments_mipadi");
+        let stored = archive
+            .store(
+                "someoneelse",
+                &Resource::Saved(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        assert_eq!(stored, 0);
+        assert_eq!(count(&archive, "someoneelse", COMMENT), 0);
+    }
+
+    #[test]
+    fn it_looks_up_users_case_insensitively() {
+        let archive = archive();
+        assert_eq!(count(&archive, "MiPaDi", COMMENT), 100);
+    }
+
+    #[test]
+    fn it_has_no_account_data_for_unknown_users() {
+        let archive = archive();
+        assert!(archive.about("testuserpleaseignore").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn it_serves_archived_history() {
+        let user = Redditor::with_service("mipadi", &archive(), &options())
+            .await
+            .unwrap();
+        assert_eq!(user.comments().count(), 100);
+        assert_eq!(user.submissions().count(), 100);
+        assert_eq!(user.link_karma(), 11729);
+    }
+
+    #[tokio::test]
+    async fn it_serves_archived_history_sorted_by_score() {
+        let params = ListingParams::default().sort(Sort::Top);
+        let data = archive()
+            .get_resource("mipadi", &Resource::Comments(params))
+            .await
+            .unwrap();
+        let comments = Comment::parse_page(&data).unwrap().into_items();
+        assert_eq!(comments.len(), 100);
+        assert!(
+            comments
+                .windows(2)
+                .all(|pair| pair[0].score() >= pair[1].score())
+        );
+    }
+
+    #[tokio::test]
+    async fn it_serves_archived_history_within_a_time_window() {
+        let params = ListingParams::default()
+            .sort(Sort::Top)
+            .time(TimeWindow::Hour);
+        let data = archive()
+            .get_resource("mipadi", &Resource::Comments(params))
+            .await
+            .unwrap();
+        let comments = Comment::parse_page(&data).unwrap().into_items();
+        assert!(comments.is_empty());
+    }
+
+    async fn archived_comments(archive: &Archive) -> Vec<Comment> {
+        let resource = Resource::Comments(ListingParams::default());
+        let data = archive.get_resource("mipadi", &resource).await.unwrap();
+        Comment::parse_page(&data).unwrap().into_items()
+    }
+
+    fn mark_first_comment_removed(archive: &Archive) -> String {
+        let data = load_data("comments_mipadi");
+        let comments = Comment::parse_page(&data).unwrap().into_items();
+        let fullname = comments[0].fullname();
+        assert_eq!(archive.mark_removed([fullname]).unwrap(), 1);
+        String::from(fullname)
+    }
+
+    #[tokio::test]
+    async fn it_leaves_out_removed_things_if_asked_to() {
+        let archive = archive().exclude_removed();
+        let fullname = mark_first_comment_removed(&archive);
+        let comments = archived_comments(&archive).await;
+        assert_eq!(comments.len(), 99);
+        assert!(comments.iter().all(|c| c.fullname() != fullname));
+    }
+
+    #[tokio::test]
+    async fn it_serves_removed_things_by_default() {
+        let archive = archive();
+        mark_first_comment_removed(&archive);
+        assert_eq!(archived_comments(&archive).await.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn it_restores_removed_things_that_are_retrieved_again() {
+        let archive = archive().exclude_removed();
+        mark_first_comment_removed(&archive);
+        let data = load_data("comments_mipadi");
+        archive
+            .store(
+                "mipadi",
+                &Resource::Comments(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        assert_eq!(archived_comments(&archive).await.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn it_keeps_the_original_text_of_removed_things() {
+        let archive = archive();
+        let data = load_data("comments_mipadi");
+        let original = Comment::parse_page(&data).unwrap().into_items().remove(0);
+        let data = load_data_with("comments_mipadi", |comments| {
+            comments[0]["body"] = "[removed]".into();
+            comments[0]["author"] = "[deleted]".into();
+        });
+        archive
+            .store(
+                "mipadi",
+                &Resource::Comments(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        let comments = archived_comments(&archive).await;
+        let archived = comments
+            .iter()
+            .find(|comment| comment.fullname() == original.fullname())
+            .unwrap();
+        assert_eq!(comments.len(), 100);
+        assert_eq!(archived.markdown_body(), original.markdown_body());
+    }
+
+    #[tokio::test]
+    async fn it_keeps_removed_things_marked_as_removed() {
+        let archive = archive().exclude_removed();
+        mark_first_comment_removed(&archive);
+        let data = load_data_with("comments_mipadi", |comments| {
+            comments[0]["body"] = "[deleted]".into();
+        });
+        archive
+            .store(
+                "mipadi",
+                &Resource::Comments(ListingParams::default()),
+                &data,
+            )
+            .unwrap();
+        assert_eq!(archived_comments(&archive).await.len(), 99);
+    }
+
+    #[test]
+    fn it_only_marks_things_as_removed_once() {
+        let archive = archive();
+        mark_first_comment_removed(&archive);
+        let data = load_data("comments_mipadi");
+        let comments = Comment::parse_page(&data).unwrap().into_items();
+        assert_eq!(archive.mark_removed([comments[0].fullname()]).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_serves_archived_accounts_without_history() {
+        let archive = Archive::in_memory().unwrap();
+        archive
+            .store("mipadi", &Resource::About, &load_data("about_mipadi"))
+            .unwrap();
+        let user = Redditor::with_service("mipadi", &archive, &options())
+            .await
+            .unwrap();
+        assert_eq!(user.comments().count(), 0);
+        assert_eq!(user.submissions().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_returns_an_error_for_unarchived_users() {
+        let user = Redditor::with_service("testuserpleaseignore", &archive(), &options()).await;
+        assert!(user.is_err(), "should be Err, was {user:?}");
+    }
+
+    #[tokio::test]
+    async fn it_archives_fetched_history() {
+        let path = env::temp_dir().join(format!("usaidwat-{}.sqlite3", Uuid::new_v4()));
+        let service =
+            ArchivingService::new(TestService::new("mipadi"), Archive::open(&path).unwrap());
+        let options = ListingOptions::default().progress(false);
+        Redditor::with_service("mipadi", &service, &options)
+            .await
+            .unwrap();
+
+        let archive = Archive::open(&path).unwrap();
+        assert_eq!(count(&archive, "mipadi", COMMENT), 100);
+        assert_eq!(count(&archive, "mipadi", SUBMISSION), 100);
+        assert_eq!(count(&archive, "mipadi", ACCOUNT), 1);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -3,22 +3,45 @@
 
 //! Drives the command-line program.
 
+use crate::archive::{Archive, ArchivingService};
+use crate::config::ConfigFile;
 use crate::count::{SortAlgorithm, SubredditCounter};
+use crate::diff::Diff;
+use crate::dump::Dump;
+use crate::export::Export;
 use crate::filter::{RedditFilter, StringSet};
 use crate::reddit::Redditor;
+use crate::reddit::account::{AccountError, AccountStatus};
+use crate::reddit::auth::{Credentials, TokenStore};
+use crate::reddit::cache::{self, CachePolicy, CachedService};
+use crate::reddit::cassette::CassetteService;
+use crate::reddit::client::ListingOptions;
+use crate::reddit::login::{self, Login};
+use crate::reddit::resource::{Sort, TimeWindow};
+use crate::reddit::service::{self, RedditService};
+use crate::reddit::thing::{Comment, CommentFlag, Removable};
+use crate::reddit::user_agent::UserAgent;
 use crate::summary::Summarizer;
 use crate::view::{ViewOptions, Viewable};
+use anyhow::{Context, anyhow};
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use clap_verbosity_flag::Verbosity;
 use cogito::prelude::*;
 use cogito_openai::OpenAIModel;
 use cogito_openai::client::OpenAIClient;
+use colored::Colorize;
 use discount::terminal;
+use futures::stream::{self, StreamExt};
 use horologe::SystemClock;
-use hypertyper::HttpClientFactory;
+use hypertyper::{HttpClientFactory, HttpError};
 use indoc::formatdoc;
-use log::{debug, info, trace};
-use std::time::Instant;
+use itertools::Itertools;
+use log::{debug, info, trace, warn};
+use reqwest::StatusCode;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use std::{fmt, result};
 use tokio_pager::{Pager, PagerEnv};
 
@@ -71,6 +94,50 @@ pub struct Config {
     #[command(flatten)]
     verbosity: Verbosity,
 
+    /// Retrieve up to N comments and posts (Reddit returns at most about 1000) [default: 100, or all with --from-archive, --from-export, or --from-dump]
+    #[arg(long, global = true, value_name = "N")]
+    max_items: Option<usize>,
+
+    /// Record Reddit API responses to a cassette file at PATH
+    #[arg(long, global = true, value_name = "PATH", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Replay Reddit API responses from a cassette file at PATH instead of contacting Reddit
+    #[arg(long, global = true, value_name = "PATH")]
+    replay: Option<PathBuf>,
+
+    /// Ignore cached Reddit API responses
+    #[arg(long, global = true)]
+    refresh: bool,
+
+    /// Only use cached Reddit API responses
+    #[arg(long, global = true, conflicts_with_all = ["record", "replay", "refresh"])]
+    offline: bool,
+
+    /// Use the user's full archived history instead of contacting Reddit
+    #[arg(long, global = true, conflicts_with_all = ["record", "replay", "no_archive"])]
+    from_archive: bool,
+
+    /// Use the comments and posts in Reddit's data export at PATH instead of contacting Reddit
+    #[arg(long, global = true, value_name = "PATH", conflicts_with_all = ["record", "replay", "from_archive"])]
+    from_export: Option<PathBuf>,
+
+    /// Use the comments and posts in the NDJSON dump files at PATH instead of contacting Reddit (may be repeated)
+    #[arg(long, global = true, value_name = "PATH", conflicts_with_all = ["record", "replay", "from_archive", "from_export"])]
+    from_dump: Vec<PathBuf>,
+
+    /// Do not add retrieved data to the local archive
+    #[arg(long, global = true)]
+    no_archive: bool,
+
+    /// Include deleted and removed comments and posts
+    #[arg(long, global = true)]
+    include_removed: bool,
+
+    /// Cache Reddit API responses for SECS seconds
+    #[arg(long, global = true, value_name = "SECS", default_value_t = cache::DEFAULT_TTL.as_secs())]
+    cache_ttl: u64,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -80,17 +147,55 @@ impl Config {
         self.verbosity
     }
 
-    pub fn username(&self) -> String {
-        String::from(self.command.username())
+    /// How cached Reddit API responses are used.
+    ///
+    /// `diff` always refreshes the cache: a cached response may be the very
+    /// snapshot it is compared against, which would hide every change.
+    fn cache_policy(&self) -> CachePolicy {
+        if self.offline {
+            CachePolicy::Offline
+        } else if self.refresh || matches!(self.command, Command::Diff { .. }) {
+            CachePolicy::Refresh
+        } else {
+            CachePolicy::Normal
+        }
+    }
+
+    /// The Reddit user the command acts on, if it acts on exactly one.
+    pub fn username(&self) -> Option<String> {
+        self.command.username().map(String::from)
+    }
+
+    /// All of the Reddit users the command acts on.
+    fn usernames(&self) -> anyhow::Result<Vec<String>> {
+        match self.command.users() {
+            Some(users) => users.usernames(),
+            None => Ok(self.username().into_iter().collect()),
+        }
+    }
+
+    /// The number of users that are retrieved at a time.
+    fn jobs(&self) -> usize {
+        self.command.users().map_or(1, |users| users.jobs.get())
     }
 }
 
 #[derive(Debug, Subcommand)]
 enum Command {
-    /// Retrieve a user's account information
-    Info {
+    /// Compare a user's current activity with their archived activity
+    Diff {
         /// Reddit username
         username: String,
+
+        /// Show dates in "absolute" or "relative" format
+        #[arg(long, value_name = "FORMAT", default_value_t)]
+        date: DateFormat,
+    },
+
+    /// Retrieve users' account information
+    Info {
+        #[command(flatten)]
+        users: UserArgs,
     },
 
     /// Display a user's comments
@@ -102,6 +207,9 @@ enum Command {
         /// Only show comments from these subreddits
         subreddits: Vec<String>,
 
+        #[command(flatten)]
+        listing: ListingArgs,
+
         /// Show dates in "absolute" or "relative" format
         #[arg(long, value_name = "FORMAT", default_value_t)]
         date: DateFormat,
@@ -121,12 +229,35 @@ enum Command {
         /// Print raw comment bodies
         #[arg(long, default_value_t = false)]
         raw: bool,
+
+        /// Show only comments with all of these flags
+        #[arg(long, value_name = "FLAGS", value_delimiter = ',')]
+        only: Vec<CommentSelector>,
+
+        /// Show up to N comments that each comment replied to
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 0,
+            conflicts_with = "oneline"
+        )]
+        context: usize,
     },
 
+    /// Log in to Reddit as an installed app
+    Login {
+        /// Listen for Reddit's redirect on this port
+        #[arg(short = 'p', long, default_value_t = Login::DEFAULT_PORT)]
+        port: u16,
+    },
+
+    /// Log out of Reddit and revoke the saved login
+    Logout,
+
     /// Display a user's submitted posts
     Posts(PostCommandConfig),
 
-    /// Summarize a user's posting history
+    /// Summarize users' posting histories
     #[clap(visible_alias = "summarize")]
     #[clap(visible_alias = "s")]
     #[clap(
@@ -134,8 +265,11 @@ enum Command {
         after_long_help = after_summary_help_long::<OpenAIClient<Service>>(),
     )]
     Summary {
-        /// Reddit username
-        username: String,
+        #[command(flatten)]
+        users: UserArgs,
+
+        #[command(flatten)]
+        listing: ListingArgs,
 
         /// Use this AI model for summarization
         #[arg(short = 'm', long, default_value_t)]
@@ -146,7 +280,7 @@ enum Command {
         include_self: bool,
     },
 
-    /// Tally a user's comments by subreddit
+    /// Tally users' comments by subreddit
     #[clap(visible_alias = "t")]
     Tally(TallyConfig),
 
@@ -158,22 +292,91 @@ enum Command {
 }
 
 impl Command {
-    pub fn username(&self) -> &str {
+    pub fn username(&self) -> Option<&str> {
         match &self {
-            Command::Info { username } => username,
-            Command::Log { username, .. } => username,
+            Command::Diff { username, .. } => Some(username),
+            Command::Info { users } => users.username(),
+            Command::Log { username, .. } => Some(username),
+            Command::Login { .. } => None,
+            Command::Logout => None,
             Command::Posts(subconfig) => subconfig.command.username(),
-            Command::Summary { username, .. } => username,
-            Command::Tally(TallyConfig { username, .. }) => username,
-            Command::Timeline { username } => username,
+            Command::Summary { users, .. } => users.username(),
+            Command::Tally(TallyConfig { users, .. }) => users.username(),
+            Command::Timeline { username } => Some(username),
+        }
+    }
+
+    /// The users the command acts on, if it accepts several.
+    fn users(&self) -> Option<&UserArgs> {
+        match &self {
+            Command::Info { users } => Some(users),
+            Command::Posts(subconfig) => subconfig.command.users(),
+            Command::Summary { users, .. } => Some(users),
+            Command::Tally(TallyConfig { users, .. }) => Some(users),
+            _ => None,
+        }
+    }
+
+    /// The number of levels of parent comments the command shows.
+    fn context(&self) -> usize {
+        match &self {
+            Command::Log { context, .. } => *context,
+            _ => 0,
+        }
+    }
+
+    /// The comments out of `comments` that the command shows, which is all
+    /// of them unless the command is `log`.
+    ///
+    /// Returns an error if `log`'s subreddit filter is invalid.
+    fn shown_comments<'a>(
+        &self,
+        comments: impl Iterator<Item = &'a Comment>,
+    ) -> result::Result<Vec<&'a Comment>, String> {
+        let Command::Log {
+            subreddits,
+            grep,
+            limit,
+            only,
+            ..
+        } = &self
+        else {
+            return Ok(comments.collect());
+        };
+
+        let filter = StringSet::from(subreddits).ok_or(format!(
+            "invalid subreddit filter: {}",
+            subreddits.join(" ")
+        ))?;
+        let flags: Vec<_> = only.iter().copied().map(CommentFlag::from).collect();
+        Ok(RedditFilter::new(comments)
+            .take(limit)
+            .grep(grep)
+            .filter(&filter)
+            .flagged(&flags)
+            .collect())
+    }
+
+    /// The options choosing which comments and posts the command retrieves,
+    /// if it accepts any.
+    fn listing(&self) -> Option<&ListingArgs> {
+        match &self {
+            Command::Log { listing, .. } => Some(listing),
+            Command::Posts(subconfig) => Some(subconfig.command.listing()),
+            Command::Summary { listing, .. } => Some(listing),
+            Command::Tally(TallyConfig { listing, .. }) => Some(listing),
+            _ => None,
         }
     }
 }
 
 #[derive(Args, Debug)]
 struct TallyConfig {
-    /// Reddit username
-    username: String,
+    #[command(flatten)]
+    users: UserArgs,
+
+    #[command(flatten)]
+    listing: ListingArgs,
 
     /// Sort output by number of comments instead of alphabetically by subreddit
     #[arg(short = 'c', long = "count", default_value_t = false)]
@@ -206,6 +409,9 @@ enum PostSubcommand {
         // Only show posts from these subreddits
         subreddits: Vec<String>,
 
+        #[command(flatten)]
+        listing: ListingArgs,
+
         /// Show or hide self posts
         #[arg(
             long = "self",
@@ -235,10 +441,174 @@ enum PostSubcommand {
 }
 
 impl PostSubcommand {
-    pub fn username(&self) -> &str {
+    pub fn username(&self) -> Option<&str> {
+        match &self {
+            PostSubcommand::Log { username, .. } => Some(username),
+            PostSubcommand::Tally(TallyConfig { users, .. }) => users.username(),
+        }
+    }
+
+    fn users(&self) -> Option<&UserArgs> {
+        match &self {
+            PostSubcommand::Log { .. } => None,
+            PostSubcommand::Tally(TallyConfig { users, .. }) => Some(users),
+        }
+    }
+
+    fn listing(&self) -> &ListingArgs {
         match &self {
-            PostSubcommand::Log { username, .. } => username,
-            PostSubcommand::Tally(TallyConfig { username, .. }) => username,
+            PostSubcommand::Log { listing, .. } => listing,
+            PostSubcommand::Tally(TallyConfig { listing, .. }) => listing,
+        }
+    }
+}
+
+/// The Reddit users a command acts on.
+#[derive(Args, Debug)]
+struct UserArgs {
+    /// Reddit usernames
+    #[arg(value_name = "USERNAME", required_unless_present = "users_from")]
+    usernames: Vec<String>,
+
+    /// Also read usernames from FILE, one per line
+    #[arg(long, value_name = "FILE")]
+    users_from: Option<PathBuf>,
+
+    /// Retrieve up to N users at a time
+    #[arg(short = 'j', long, value_name = "N", default_value_t = UserArgs::DEFAULT_JOBS)]
+    jobs: NonZeroUsize,
+}
+
+impl UserArgs {
+    const DEFAULT_JOBS: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+    /// The username, if exactly one user was given on the command line.
+    fn username(&self) -> Option<&str> {
+        match (self.usernames.as_slice(), &self.users_from) {
+            ([username], None) => Some(username),
+            _ => None,
+        }
+    }
+
+    /// All of the usernames, including the ones read from `--users-from`,
+    /// without duplicates.
+    fn usernames(&self) -> anyhow::Result<Vec<String>> {
+        let mut usernames = self.usernames.clone();
+        if let Some(path) = &self.users_from {
+            let contents = fs::read_to_string(path)
+                .with_context(|| format!("could not read usernames from {}", path.display()))?;
+            usernames.extend(parse_usernames(&contents));
+        }
+        Ok(usernames.into_iter().unique().collect())
+    }
+}
+
+/// Parses a list of usernames, one per line. Blank lines and lines
+/// starting with `#` are ignored, as is a leading `u/` or `/u/`.
+fn parse_usernames(contents: &str) -> impl Iterator<Item = String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let line = line.strip_prefix('/').unwrap_or(line);
+            let line = line.strip_prefix("u/").unwrap_or(line);
+            String::from(line)
+        })
+}
+
+/// Options that choose which of a user's comments and posts are retrieved.
+#[derive(Args, Debug, Default)]
+struct ListingArgs {
+    /// Retrieve comments and posts in this order
+    #[arg(long, value_name = "ORDER")]
+    sort: Option<ListingOrder>,
+
+    /// Retrieve top or controversial comments and posts from this time window
+    #[arg(long, value_name = "WINDOW", requires = "sort")]
+    time: Option<ListingWindow>,
+}
+
+impl ListingArgs {
+    /// Applies the listing arguments to a set of listing `options`.
+    fn apply(&self, options: ListingOptions) -> ListingOptions {
+        options
+            .sort(self.sort.map(Sort::from))
+            .time(self.time.map(TimeWindow::from))
+    }
+}
+
+/// The order in which comments and posts are retrieved.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum ListingOrder {
+    /// Newest items first.
+    New,
+
+    /// Items that are currently popular first.
+    Hot,
+
+    /// Highest-scoring items first.
+    Top,
+
+    /// Most controversial items first.
+    Controversial,
+}
+
+impl fmt::Display for ListingOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Sort::from(*self))
+    }
+}
+
+impl From<ListingOrder> for Sort {
+    fn from(order: ListingOrder) -> Self {
+        match order {
+            ListingOrder::New => Sort::New,
+            ListingOrder::Hot => Sort::Hot,
+            ListingOrder::Top => Sort::Top,
+            ListingOrder::Controversial => Sort::Controversial,
+        }
+    }
+}
+
+/// The period of time from which top or controversial comments and posts
+/// are retrieved.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum ListingWindow {
+    /// The past hour.
+    Hour,
+
+    /// The past day.
+    Day,
+
+    /// The past week.
+    Week,
+
+    /// The past month.
+    Month,
+
+    /// The past year.
+    Year,
+
+    /// All time.
+    All,
+}
+
+impl fmt::Display for ListingWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", TimeWindow::from(*self))
+    }
+}
+
+impl From<ListingWindow> for TimeWindow {
+    fn from(window: ListingWindow) -> Self {
+        match window {
+            ListingWindow::Hour => TimeWindow::Hour,
+            ListingWindow::Day => TimeWindow::Day,
+            ListingWindow::Week => TimeWindow::Week,
+            ListingWindow::Month => TimeWindow::Month,
+            ListingWindow::Year => TimeWindow::Year,
+            ListingWindow::All => TimeWindow::All,
         }
     }
 }
@@ -288,6 +658,51 @@ impl fmt::Display for SelfVisibility {
     }
 }
 
+/// Selects comments in log output by something notable about them.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum CommentSelector {
+    /// The comment has been edited.
+    Edited,
+
+    /// The comment was posted by the author of the post it belongs to.
+    Op,
+
+    /// The comment was distinguished by a moderator.
+    Mod,
+
+    /// The comment was posted in a post marked NSFW.
+    Nsfw,
+
+    /// The comment has many upvotes and many downvotes.
+    Controversial,
+
+    /// The comment has received an award.
+    Gilded,
+
+    /// The comment is pinned to the top of its post.
+    Stickied,
+}
+
+impl fmt::Display for CommentSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", CommentFlag::from(*self))
+    }
+}
+
+impl From<CommentSelector> for CommentFlag {
+    fn from(selector: CommentSelector) -> Self {
+        match selector {
+            CommentSelector::Edited => CommentFlag::Edited,
+            CommentSelector::Op => CommentFlag::Op,
+            CommentSelector::Mod => CommentFlag::Mod,
+            CommentSelector::Nsfw => CommentFlag::Nsfw,
+            CommentSelector::Controversial => CommentFlag::Controversial,
+            CommentSelector::Gilded => CommentFlag::Gilded,
+            CommentSelector::Stickied => CommentFlag::Stickied,
+        }
+    }
+}
+
 /// Determines the qualities of the AI model used for summarization.
 #[derive(Clone, Debug, Default, ValueEnum)]
 enum AIModelClass {
@@ -340,39 +755,340 @@ impl AIModelClass {
 #[derive(Debug)]
 pub struct Runner {
     config: Config,
-    user: Redditor,
+    users: Vec<Redditor>,
+    previous: Option<Redditor>,
 }
 
 impl Runner {
     /// Create a new program runner using the given `config`.
     ///
     /// Returns an error with a helpful message if the user does not exist.
+    /// If the command acts on several users, users that cannot be loaded
+    /// are reported and skipped, and an error is only returned if none of
+    /// them can be loaded.
     pub async fn new(config: Config) -> anyhow::Result<Runner> {
-        let username = config.command.username();
-        let user = Redditor::new(username).await?;
-        Ok(Self { config, user })
+        // The archived snapshot has to be read before the user is loaded,
+        // since loading the user adds their current activity to the archive.
+        let previous = match &config.command {
+            Command::Diff { username, .. } => {
+                if config.from_archive {
+                    return Err(anyhow!("diff cannot be used with --from-archive"));
+                }
+                if config.from_export.is_some() {
+                    return Err(anyhow!("diff cannot be used with --from-export"));
+                }
+                if !config.from_dump.is_empty() {
+                    return Err(anyhow!("diff cannot be used with --from-dump"));
+                }
+                if config.offline {
+                    return Err(anyhow!("diff cannot be used with --offline"));
+                }
+                let options = ListingOptions::default().max_items(usize::MAX);
+                Some(Self::load_archived(username, &options).await?)
+            }
+            _ => None,
+        };
+        let usernames = config.usernames()?;
+        let users = if usernames.is_empty() {
+            vec![]
+        } else {
+            Self::load(&config, &usernames).await?
+        };
+        // Diff reports deletions, so it needs to see deleted things.
+        let users = if config.include_removed || matches!(config.command, Command::Diff { .. }) {
+            users
+        } else {
+            users.into_iter().map(Redditor::without_removed).collect()
+        };
+        Ok(Self {
+            config,
+            users,
+            previous,
+        })
+    }
+
+    /// Loads the Redditors with the given `usernames`, recording or
+    /// replaying API responses if asked to, and otherwise caching them.
+    ///
+    /// Everything retrieved from Reddit is added to the local archive,
+    /// unless archiving is turned off.
+    async fn load(config: &Config, usernames: &[String]) -> anyhow::Result<Vec<Redditor>> {
+        let listing = config.command.listing();
+        let options = |max_items| {
+            let options = ListingOptions::default().max_items(max_items);
+            // Progress indicators for several users at once would
+            // overwrite each other.
+            let options = if usernames.len() > 1 {
+                options.progress(false)
+            } else {
+                options
+            };
+            match listing {
+                Some(listing) => listing.apply(options),
+                None => options,
+            }
+        };
+
+        if let Some(path) = &config.from_export {
+            let [username] = usernames else {
+                return Err(anyhow!("--from-export can only be used with one user"));
+            };
+            let max_items = config.max_items.unwrap_or(usize::MAX);
+            let export = Export::open(path)?.max_items(max_items);
+            return Ok(vec![export.redditor(username)?]);
+        }
+
+        if !config.from_dump.is_empty() {
+            let [username] = usernames else {
+                return Err(anyhow!("--from-dump can only be used with one user"));
+            };
+            let max_items = config.max_items.unwrap_or(usize::MAX);
+            let dump = Dump::new(&config.from_dump).max_items(max_items);
+            return Ok(vec![dump.redditor(username)?]);
+        }
+
+        if config.from_archive {
+            let max_items = config.max_items.unwrap_or(usize::MAX);
+            let archive = Archive::open_default()?;
+            let users = Self::load_each(
+                config,
+                usernames,
+                &archive,
+                &options(max_items),
+                not_archived,
+            )
+            .await;
+            return Self::loaded(usernames, users);
+        }
+
+        let max_items = config
+            .max_items
+            .unwrap_or(ListingOptions::DEFAULT_MAX_ITEMS);
+        let options = options(max_items);
+        let users = match (&config.record, &config.replay) {
+            (Some(path), _) => {
+                let service = CassetteService::record(Self::reddit_service()?, path);
+                Self::load_archiving(config, usernames, service, &options).await
+            }
+            (_, Some(path)) => {
+                let service = CassetteService::replay(path)?;
+                let describe = |username: &str, err: anyhow::Error| {
+                    let Some(uri) = service.unrecorded(username) else {
+                        return err;
+                    };
+                    anyhow!(
+                        "no response for {uri} was recorded in {}; record it again with --record",
+                        service.path().display()
+                    )
+                };
+                Self::load_each(config, usernames, &service, &options, describe).await
+            }
+            _ => {
+                // Offline, Reddit is never contacted, so its network settings
+                // do not matter.
+                let reddit_service = || {
+                    if config.offline {
+                        Ok(RedditService::default())
+                    } else {
+                        Self::reddit_service()
+                    }
+                };
+                match CachedService::in_default_location(reddit_service()?) {
+                    Some(service) => {
+                        let service = service
+                            .ttl(Duration::from_secs(config.cache_ttl))
+                            .policy(config.cache_policy());
+                        Self::load_archiving(config, usernames, service, &options).await
+                    }
+                    None if config.offline => Err(anyhow!(
+                        "--offline cannot be used without a cache directory"
+                    )),
+                    None => {
+                        let service = reddit_service()?;
+                        Self::load_archiving(config, usernames, service, &options).await
+                    }
+                }
+            }
+        };
+        Self::loaded(usernames, users)
+    }
+
+    /// Creates a service for contacting Reddit.
+    ///
+    /// Returns an error if the User-Agent or network settings in the
+    /// configuration file are invalid, rather than quietly using the
+    /// defaults.
+    fn reddit_service() -> anyhow::Result<RedditService> {
+        UserAgent::load()?;
+        RedditService::http_client()?;
+        Ok(RedditService::default())
+    }
+
+    /// Loads the Redditor's comments and posts from the local archive,
+    /// leaving out the ones an earlier diff found to have been removed.
+    async fn load_archived(username: &str, options: &ListingOptions) -> anyhow::Result<Redditor> {
+        let archive = Archive::open_default()?.exclude_removed();
+        Redditor::with_service(username, &archive, options)
+            .await
+            .map_err(|err| not_archived(username, err))
+    }
+
+    /// Loads the Redditors with the given `usernames` from `service`, adding
+    /// everything it retrieves to the local archive if archiving is on.
+    async fn load_archiving<S: service::Service + Sync>(
+        config: &Config,
+        usernames: &[String],
+        service: S,
+        options: &ListingOptions,
+    ) -> Vec<anyhow::Result<Redditor>> {
+        let describe = |username: &str, err: anyhow::Error| match err.downcast_ref::<HttpError>() {
+            Some(HttpError::Http(StatusCode::GATEWAY_TIMEOUT)) if config.offline => {
+                anyhow!("no cached data for {username}; try again without --offline")
+            }
+            _ => err,
+        };
+
+        let archive = if config.no_archive {
+            None
+        } else {
+            Archive::open_default()
+                .inspect_err(|err| warn!("Not archiving: {err:#}"))
+                .ok()
+        };
+        match archive {
+            Some(archive) => {
+                let service = ArchivingService::new(service, archive);
+                Self::load_each(config, usernames, &service, options, describe).await
+            }
+            None => Self::load_each(config, usernames, &service, options, describe).await,
+        }
+    }
+
+    /// Loads each of the Redditors with the given `usernames` from
+    /// `service`, retrieving up to `--jobs` of them at a time.
+    ///
+    /// Every user shares the same `service`, so requests for all of them
+    /// are kept within the same rate limit. Errors are passed through
+    /// `describe`, along with the username of the user who could not be
+    /// loaded. Results are returned in the same order as `usernames`.
+    async fn load_each<S: service::Service + Sync>(
+        config: &Config,
+        usernames: &[String],
+        service: &S,
+        options: &ListingOptions,
+        describe: impl Fn(&str, anyhow::Error) -> anyhow::Error,
+    ) -> Vec<anyhow::Result<Redditor>> {
+        let describe = &describe;
+        stream::iter(usernames)
+            .map(|username| async move {
+                match Redditor::with_service(username, service, options).await {
+                    Ok(redditor) => Ok(Self::with_context(config, redditor, service).await),
+                    Err(err) => Err(describe(username, err)),
+                }
+            })
+            .buffered(config.jobs())
+            .collect()
+            .await
+    }
+
+    /// Retrieves the context the command shows for `redditor`'s comments.
+    ///
+    /// Context is only retrieved for the comments the command will actually
+    /// show, which may be only a few of the ones that were loaded.
+    async fn with_context<S: service::Service + Sync>(
+        config: &Config,
+        redditor: Redditor,
+        service: &S,
+    ) -> Redditor {
+        redditor
+            .retrieve_context(service, config.command.context(), |redditor| {
+                let comments = redditor
+                    .comments()
+                    .filter(|comment| config.include_removed || !comment.is_removed());
+                config.command.shown_comments(comments).unwrap_or_default()
+            })
+            .await
+    }
+
+    /// Keeps the Redditors that could be loaded.
+    ///
+    /// If only one user was requested, any error loading them is returned.
+    /// Otherwise, users that could not be loaded are reported and skipped,
+    /// unless none of them could be loaded, in which case the first error
+    /// is returned.
+    fn loaded(
+        usernames: &[String],
+        results: Vec<anyhow::Result<Redditor>>,
+    ) -> anyhow::Result<Vec<Redditor>> {
+        if results.len() == 1 {
+            return results.into_iter().collect();
+        }
+
+        let mut users = vec![];
+        let mut errors = vec![];
+        for (username, result) in usernames.iter().zip(results) {
+            match result {
+                Ok(user) => users.push(user),
+                Err(err) => errors.push((username, err)),
+            }
+        }
+
+        // If nobody could be loaded, the first error is returned instead
+        // of being reported here. It names the user, since the caller only
+        // knows the username when there is a single user.
+        let first_err = if users.is_empty() && !errors.is_empty() {
+            let (username, err) = errors.remove(0);
+            Some(err.context(format!("could not load {username}")))
+        } else {
+            None
+        };
+        for (username, err) in errors {
+            eprintln!("Skipping {username}: {err:#}");
+        }
+        match first_err {
+            Some(err) => Err(err),
+            None => Ok(users),
+        }
     }
 
     fn user(&self) -> &Redditor {
-        &self.user
+        self.users
+            .first()
+            .expect("command should have a Reddit user")
+    }
+
+    /// Joins the `output` for each user, under a header naming the user if
+    /// there is more than one.
+    fn group_by_user<'a>(&self, output: impl Iterator<Item = (&'a Redditor, String)>) -> String {
+        if self.users.len() == 1 {
+            return output.map(|(_, output)| output).join("\n");
+        }
+        output
+            .map(|(user, output)| format!("{}\n{output}", user.username().bold()))
+            .join("\n\n")
     }
 
     /// Run the command-line program using its stored configuration options.
     pub async fn run(&self) -> Result {
         match &self.config.command {
+            Command::Diff { date, .. } => self.run_diff(date).await,
             Command::Info { .. } => self.run_info(),
             Command::Log {
-                subreddits,
                 date,
                 grep,
-                limit,
                 oneline,
                 raw,
                 ..
             } => {
-                self.run_log(subreddits, date, grep, limit, oneline, raw)
-                    .await
+                let opts = ViewOptions::default()
+                    .oneline(*oneline)
+                    .raw(*raw)
+                    .grep(grep.clone())
+                    .date_format(date.clone());
+                self.run_log(oneline, &opts).await
             }
+            Command::Login { port } => self.run_login(*port).await,
+            Command::Logout => self.run_logout().await,
             Command::Posts(subconfig) => self.run_posts(subconfig).await,
             Command::Summary {
                 model,
@@ -384,45 +1100,55 @@ impl Runner {
         }
     }
 
+    async fn run_diff(&self, date_format: &DateFormat) -> Result {
+        let previous = self
+            .previous
+            .as_ref()
+            .expect("diff should have an archived snapshot");
+        let diff = Diff::between(previous, self.user());
+
+        // Removals are recorded so that the next diff does not report them
+        // again.
+        if !self.config.no_archive
+            && let Err(err) =
+                Archive::open_default().and_then(|archive| archive.mark_removed(diff.removed()))
+        {
+            warn!("Could not record removals in the archive: {err:#}");
+        }
+
+        if diff.is_empty() {
+            println!(
+                "{} has not changed since they were archived.",
+                self.user().username()
+            );
+            return Ok(());
+        }
+
+        let opts = ViewOptions::default().date_format(date_format.clone());
+        let output = diff.view(&opts, &SystemClock::default());
+        Pager::new(PagerEnv::default()).page(&output).await
+    }
+
     fn run_info(&self) -> Result {
-        println!(
-            "{}",
-            self.user()
-                .view(&ViewOptions::default(), &SystemClock::default())
-        );
+        let output = self.group_by_user(self.users.iter().map(|user| {
+            let info = user.view(&ViewOptions::default(), &SystemClock::default());
+            (user, info)
+        }));
+        println!("{output}");
         Ok(())
     }
 
-    async fn run_log(
-        &self,
-        subreddits: &Vec<String>,
-        date_format: &DateFormat,
-        grep: &Option<String>,
-        limit: &Option<u32>,
-        oneline: &bool,
-        raw: &bool,
-    ) -> Result {
-        let opts = ViewOptions::default()
-            .oneline(*oneline)
-            .raw(*raw)
-            .grep(grep.clone())
-            .date_format(date_format.clone());
-
-        let filter = StringSet::from(subreddits).ok_or(format!(
-            "invalid subreddit filter: {}",
-            subreddits.join(" ")
-        ))?;
-
-        let comments = RedditFilter::new(self.user().comments())
-            .take(limit)
-            .grep(grep)
-            .filter(&filter)
-            .collect();
+    async fn run_log(&self, oneline: &bool, opts: &ViewOptions) -> Result {
+        let comments = self.config.command.shown_comments(self.user().comments())?;
 
         let joiner = if *oneline { "\n" } else { "\n\n\n" };
         let output = comments
             .iter()
-            .map(|comment| comment.view(&opts, &SystemClock::default()))
+            .map(|comment| {
+                self.user()
+                    .thread(comment)
+                    .view(opts, &SystemClock::default())
+            })
             .collect::<Vec<_>>()
             .join(joiner);
 
@@ -431,6 +1157,43 @@ impl Runner {
             .await
     }
 
+    fn login_context() -> result::Result<(Credentials, TokenStore), String> {
+        RedditService::http_client().map_err(|err| format!("{err:#}"))?;
+        let credentials = Credentials::load().ok_or(include_str!("help/reddit.txt").trim_end())?;
+        let store =
+            TokenStore::default_location().ok_or("Could not determine where to save the login.")?;
+        Ok((credentials, store))
+    }
+
+    async fn run_login(&self, port: u16) -> Result {
+        let (credentials, store) = Self::login_context()?;
+
+        let login = Login::bind(credentials, port)
+            .await
+            .map_err(|err| format!("Could not listen on port {port}: {err}"))?;
+        debug!("Listening for redirect at {}", login.redirect_uri());
+
+        println!("Open this URL in your browser to log in to Reddit:\n");
+        println!("    {}\n", login.authorization_uri());
+        println!("Waiting for authorization...");
+
+        login
+            .finish(&store)
+            .await
+            .map_err(|err| format!("Could not log in: {err:#}"))?;
+        println!("Logged in.");
+        Ok(())
+    }
+
+    async fn run_logout(&self) -> Result {
+        let (credentials, store) = Self::login_context()?;
+        login::logout(&credentials, &store)
+            .await
+            .map_err(|err| format!("Could not log out: {err:#}"))?;
+        println!("Logged out.");
+        Ok(())
+    }
+
     async fn run_posts(&self, config: &PostCommandConfig) -> Result {
         match &config.command {
             PostSubcommand::Log {
@@ -485,37 +1248,78 @@ impl Runner {
     fn run_posts_tally(&self, sort_algorithm: &SortAlgorithm) -> Result {
         // TODO: Need to test this conditional logic
 
-        if self.user().has_submissions() {
-            let posts = self.user().submissions();
-            let tallies = posts.collect::<SubredditCounter>().sort_by(sort_algorithm);
-            println!(
-                "{}",
+        let output = self.group_by_user(self.users.iter().map(|user| {
+            let tally = if user.has_submissions() {
+                let posts = user.submissions();
+                let tallies = posts.collect::<SubredditCounter>().sort_by(sort_algorithm);
                 tallies.view(&ViewOptions::default(), &SystemClock::default())
-            );
-            Ok(())
-        } else {
-            println!("{} has no posts.", self.user().username());
-            Ok(())
-        }
+            } else {
+                format!("{} has no posts.", user.username())
+            };
+            (user, tally)
+        }));
+        println!("{output}");
+        Ok(())
     }
 
     async fn run_summary(&self, model: &AIModelClass, include_self: &bool) -> Result {
         let auth =
             Auth::from_env("OPENAI_API_KEY").map_err(|_| include_str!("help/summary.txt"))?;
 
+        // The factory's clients cannot be configured directly, so they only
+        // use the proxy settings that main() exports to the environment.
+        // Rather than quietly ignore the other settings, refuse to run.
+        let file = ConfigFile::load().map_err(|err| format!("{err:#}"))?;
+        let unsupported = file.network().unexported_settings();
+        if !unsupported.is_empty() {
+            return Err(format!(
+                "summary cannot apply these [network] settings to requests to OpenAI: {}",
+                unsupported.join(", ")
+            ));
+        }
         let factory = HttpClientFactory::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
         let client = OpenAIClient::new(auth, factory);
 
-        let summarizer = Summarizer::new(client, self.user());
+        let model = model.model();
+        debug!("Using model: {:?} - {}", model, model);
+
+        // Every user is summarized with the same client. Users whose
+        // summaries fail are reported and skipped, like users who cannot
+        // be loaded.
+        let mut summarizer = Summarizer::new(client, self.user()).model(model);
         info!("Instructions:\n{}", summarizer.instructions());
+        let mut summaries = vec![];
+        let mut errors = vec![];
+        for user in &self.users {
+            summarizer = summarizer.user(user);
+            match Self::summarize(&summarizer, include_self).await {
+                Ok(summary) => summaries.push((user, summary)),
+                Err(err) => errors.push((user.username(), err)),
+            }
+        }
+
+        // If nobody could be summarized, the first error is returned instead
+        // of being reported here.
+        if summaries.is_empty() && !errors.is_empty() {
+            let (username, err) = errors.remove(0);
+            return Err(format!("Could not summarize {username}: {err}"));
+        }
+        for (username, err) in errors {
+            eprintln!("Skipping {username}: {err}");
+        }
+        let output = self.group_by_user(summaries.into_iter());
+        Pager::new(PagerEnv::default()).page(&output).await
+    }
+
+    async fn summarize<C: AiClient>(
+        summarizer: &Summarizer<'_, C>,
+        include_self: &bool,
+    ) -> result::Result<String, String> {
         debug!(
             "Summarization output:\n{}",
             summarizer.context(*include_self)
         );
 
-        let model = model.model();
-        debug!("Using model: {:?} - {}", model, model);
-
         // TODO: Should we return raw JSON here in debug mode?
 
         // TODO: Track timing in Summarizer and print stats in debug or trace mode
@@ -527,33 +1331,31 @@ impl Runner {
 
         let now = Instant::now();
         let output = summarizer
-            .model(model)
             .summarize(*include_self)
             .await
             .map_err(|err| format!("Error in API request: {err}"))?;
         let elapsed = now.elapsed();
         trace!("Summarization time: {:.4} secs", elapsed.as_secs_f64());
-        let output = textwrap::fill(&output, terminal::termwidth());
-        Pager::new(PagerEnv::default()).page(&output).await
+        Ok(textwrap::fill(&output, terminal::termwidth()))
     }
 
     fn run_tally(&self, sort_algorithm: &SortAlgorithm) -> Result {
         // TODO: Need to test this conditional logic
 
-        if self.user.has_comments() {
-            let comments = self.user().comments();
-            let tallies = comments
-                .collect::<SubredditCounter>()
-                .sort_by(sort_algorithm);
-            println!(
-                "{}",
+        let output = self.group_by_user(self.users.iter().map(|user| {
+            let tally = if user.has_comments() {
+                let comments = user.comments();
+                let tallies = comments
+                    .collect::<SubredditCounter>()
+                    .sort_by(sort_algorithm);
                 tallies.view(&ViewOptions::default(), &SystemClock::default())
-            );
-            Ok(())
-        } else {
-            println!("{} has no comments.", self.user().username());
-            Ok(())
-        }
+            } else {
+                format!("{} has no comments.", user.username())
+            };
+            (user, tally)
+        }));
+        println!("{output}");
+        Ok(())
     }
 
     fn run_timeline(&self) -> Result {
@@ -576,8 +1378,107 @@ impl Runner {
     }
 }
 
+/// Explains that a user whose account is not in the archive has not been
+/// archived yet.
+fn not_archived(username: &str, err: anyhow::Error) -> anyhow::Error {
+    match err.downcast_ref::<AccountError>() {
+        Some(err) if err.status() == AccountStatus::NotFound => {
+            anyhow!("{username} has not been archived yet")
+        }
+        _ => err,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    mod cache_policy {
+        use super::super::Config;
+        use crate::reddit::cache::CachePolicy;
+        use clap::Parser;
+
+        #[test]
+        fn it_uses_the_cache_normally() {
+            let config = Config::parse_from(["usaidwat", "log", "mipadi"]);
+            assert_eq!(config.cache_policy(), CachePolicy::Normal);
+        }
+
+        #[test]
+        fn it_refreshes_the_cache_for_diff() {
+            // A second diff within the cache's time to live must still see
+            // Reddit's current data, not the response cached by the first.
+            let config = Config::parse_from(["usaidwat", "diff", "mipadi"]);
+            assert_eq!(config.cache_policy(), CachePolicy::Refresh);
+        }
+
+        #[test]
+        fn it_only_uses_the_cache_offline() {
+            let config = Config::parse_from(["usaidwat", "--offline", "log", "mipadi"]);
+            assert_eq!(config.cache_policy(), CachePolicy::Offline);
+        }
+
+        #[test]
+        fn it_rejects_other_sources_offline() {
+            for flag in ["--refresh", "--record=mipadi.json", "--replay=mipadi.json"] {
+                let config =
+                    Config::try_parse_from(["usaidwat", "--offline", flag, "log", "mipadi"]);
+                assert!(config.is_err(), "{flag} should conflict with --offline");
+            }
+        }
+    }
+
+    mod parse_usernames {
+        use super::super::parse_usernames;
+
+        #[test]
+        fn it_parses_one_username_per_line() {
+            let usernames: Vec<_> = parse_usernames("mipadi\n  spez \nkn0thing\n").collect();
+            assert_eq!(usernames, ["mipadi", "spez", "kn0thing"]);
+        }
+
+        #[test]
+        fn it_skips_blank_lines_and_comments() {
+            let usernames: Vec<_> = parse_usernames("# mod queue\nmipadi\n\n   \nspez").collect();
+            assert_eq!(usernames, ["mipadi", "spez"]);
+        }
+
+        #[test]
+        fn it_strips_user_prefixes() {
+            let usernames: Vec<_> = parse_usernames("u/mipadi\n/u/spez").collect();
+            assert_eq!(usernames, ["mipadi", "spez"]);
+        }
+    }
+
+    mod value_enums {
+        use super::super::{CommentSelector, ListingOrder, ListingWindow};
+        use crate::reddit::resource::{Sort, TimeWindow};
+        use crate::reddit::thing::CommentFlag;
+        use clap::ValueEnum;
+
+        #[test]
+        fn it_selects_sort_orders() {
+            for order in ListingOrder::value_variants() {
+                let parsed = ListingOrder::from_str(&order.to_string(), false).unwrap();
+                assert_eq!(Sort::from(parsed).to_string(), order.to_string());
+            }
+        }
+
+        #[test]
+        fn it_selects_time_windows() {
+            for window in ListingWindow::value_variants() {
+                let parsed = ListingWindow::from_str(&window.to_string(), false).unwrap();
+                assert_eq!(TimeWindow::from(parsed).to_string(), window.to_string());
+            }
+        }
+
+        #[test]
+        fn it_selects_comment_flags() {
+            for selector in CommentSelector::value_variants() {
+                let parsed = CommentSelector::from_str(&selector.to_string(), false).unwrap();
+                assert_eq!(CommentFlag::from(parsed).to_string(), selector.to_string());
+            }
+        }
+    }
+
     mod ai_model_class {
         use super::super::AIModelClass;
         use cogito::AiModel;
@@ -0,0 +1,357 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! User configuration.
+//!
+//! Settings that are awkward to pass on the command line every time, such
+//! as Reddit API credentials, can be stored in a [TOML] file named
+//! `config.toml` in the `usaidwat` subdirectory of the user's configuration
+//! directory (usually `~/.config/usaidwat/config.toml`):
+//!
+//! ```toml
+//! [reddit]
+//! client_id = "my client id"
+//! client_secret = "my client secret"
+//! username = "my reddit username"
+//! password = "my reddit password"
+//! app_name = "my app name"
+//! contact = "reddit username of the app's operator"
+//!
+//! [network]
+//! proxy = "http://proxy.example.com:3128"
+//! no_proxy = ["localhost", ".example.com"]
+//! ca_certificates = ["/etc/ssl/certs/corporate-ca.pem"]
+//! timeout = 30
+//! connect_timeout = 10
+//! ```
+//!
+//! [TOML]: https://toml.io/
+
+use anyhow::Context;
+use hypertyper::HttpClient;
+use reqwest::{Certificate, Client, NoProxy, Proxy};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Settings read from the user's configuration file.
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigFile {
+    #[serde(default)]
+    reddit: RedditConfig,
+
+    #[serde(default)]
+    network: NetworkConfig,
+}
+
+/// Settings for connecting to the Reddit API.
+#[derive(Debug, Default, Deserialize)]
+pub struct RedditConfig {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    app_name: Option<String>,
+    contact: Option<String>,
+}
+
+/// Settings for connecting to the network, such as a proxy server.
+#[derive(Debug, Default, Deserialize)]
+pub struct NetworkConfig {
+    proxy: Option<String>,
+    #[serde(default)]
+    no_proxy: Vec<String>,
+    #[serde(default)]
+    ca_certificates: Vec<PathBuf>,
+    timeout: Option<u64>,
+    connect_timeout: Option<u64>,
+}
+
+impl ConfigFile {
+    /// The location of the configuration file, if the user's configuration
+    /// directory can be determined.
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("usaidwat").join("config.toml"))
+    }
+
+    /// Loads the configuration file from its default [location](ConfigFile::path()).
+    ///
+    /// A missing configuration file is not an error; an empty configuration
+    /// is returned instead. Returns an `Err` result if the file exists but
+    /// cannot be read or parsed.
+    pub fn load() -> anyhow::Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::from_file(path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Loads a configuration file from the given `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        Self::parse(&data).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub(crate) fn parse(data: &str) -> anyhow::Result<Self> {
+        Ok(toml::from_str(data)?)
+    }
+
+    /// Settings for connecting to the Reddit API.
+    pub fn reddit(&self) -> &RedditConfig {
+        &self.reddit
+    }
+
+    /// Settings for connecting to the network.
+    pub fn network(&self) -> &NetworkConfig {
+        &self.network
+    }
+}
+
+impl RedditConfig {
+    /// The client ID of the user's Reddit app.
+    pub fn client_id(&self) -> Option<&str> {
+        self.client_id.as_deref()
+    }
+
+    /// The client secret of the user's Reddit app.
+    pub fn client_secret(&self) -> Option<&str> {
+        self.client_secret.as_deref()
+    }
+
+    /// The Reddit username used to authenticate a script app.
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    /// The Reddit password used to authenticate a script app.
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// The app name sent to Reddit in the [User-Agent](crate::reddit::user_agent).
+    pub fn app_name(&self) -> Option<&str> {
+        self.app_name.as_deref()
+    }
+
+    /// The Reddit username of the app's operator, sent to Reddit in the
+    /// [User-Agent](crate::reddit::user_agent).
+    pub fn contact(&self) -> Option<&str> {
+        self.contact.as_deref()
+    }
+}
+
+impl NetworkConfig {
+    /// The URL of the proxy server through which all requests are sent.
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    /// Hosts and domains that are contacted directly instead of through
+    /// the proxy server.
+    pub fn no_proxy(&self) -> &[String] {
+        &self.no_proxy
+    }
+
+    /// PEM files containing root certificates that are trusted in addition
+    /// to the system's.
+    pub fn ca_certificates(&self) -> &[PathBuf] {
+        &self.ca_certificates
+    }
+
+    /// How long to wait for a request to complete.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// How long to wait for a connection to be established.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout.map(Duration::from_secs)
+    }
+
+    /// Creates an HTTP client that identifies itself as `user_agent` and
+    /// uses these settings.
+    ///
+    /// Returns an error if the proxy URL is invalid or a certificate file
+    /// cannot be read or parsed.
+    pub fn client(&self, user_agent: &str) -> anyhow::Result<HttpClient> {
+        let mut builder = Client::builder().user_agent(user_agent);
+        if let Some(proxy) = self.proxy() {
+            let proxy = Proxy::all(proxy)
+                .with_context(|| format!("invalid proxy URL: {proxy}"))?
+                .no_proxy(NoProxy::from_string(&self.no_proxy.join(",")));
+            builder = builder.proxy(proxy);
+        }
+        for path in self.ca_certificates() {
+            let pem =
+                fs::read(path).with_context(|| format!("could not read {}", path.display()))?;
+            let certs = Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("could not parse {}", path.display()))?;
+            builder = builder.tls_certs_merge(certs);
+        }
+        if let Some(timeout) = self.timeout() {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout() {
+            builder = builder.connect_timeout(timeout);
+        }
+        Ok(builder.build()?)
+    }
+
+    /// The names of the settings that are set but cannot be
+    /// [exported](NetworkConfig::export_proxy()) to the environment, so
+    /// they only apply to clients created by [`NetworkConfig::client()`].
+    pub fn unexported_settings(&self) -> Vec<&'static str> {
+        let mut settings = vec![];
+        if !self.ca_certificates.is_empty() {
+            settings.push("ca_certificates");
+        }
+        if self.timeout.is_some() {
+            settings.push("timeout");
+        }
+        if self.connect_timeout.is_some() {
+            settings.push("connect_timeout");
+        }
+        settings
+    }
+
+    /// Exports the proxy settings as the `$HTTPS_PROXY`, `$HTTP_PROXY`,
+    /// and `$NO_PROXY` environment variables, for HTTP clients that are
+    /// not created by [`NetworkConfig::client()`] but honor them.
+    ///
+    /// Variables that are already set are left alone.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as [`env::set_var()`]: it must be
+    /// called before any other threads are started.
+    pub unsafe fn export_proxy(&self) {
+        let Some(proxy) = self.proxy() else {
+            return;
+        };
+        let no_proxy = self.no_proxy.join(",");
+        let mut vars = vec![("HTTPS_PROXY", proxy), ("HTTP_PROXY", proxy)];
+        if !no_proxy.is_empty() {
+            vars.push(("NO_PROXY", &no_proxy));
+        }
+        for (var, value) in vars {
+            if env::var_os(var).is_none() && env::var_os(var.to_lowercase()).is_none() {
+                // SAFETY: Guaranteed by the caller.
+                unsafe { env::set_var(var, value) };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn it_parses_reddit_settings() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [reddit]
+            client_id = "abc"
+            client_secret = "xyz"
+            username = "mipadi"
+            password = "hunter2"
+        "#})
+        .unwrap();
+        assert_eq!(config.reddit().client_id(), Some("abc"));
+        assert_eq!(config.reddit().client_secret(), Some("xyz"));
+        assert_eq!(config.reddit().username(), Some("mipadi"));
+        assert_eq!(config.reddit().password(), Some("hunter2"));
+    }
+
+    #[test]
+    fn it_parses_an_empty_file() {
+        let config = ConfigFile::parse("").unwrap();
+        assert!(config.reddit().client_id().is_none());
+        assert!(config.reddit().client_secret().is_none());
+        assert!(config.reddit().username().is_none());
+        assert!(config.reddit().password().is_none());
+        assert!(config.network().proxy().is_none());
+        assert!(config.network().ca_certificates().is_empty());
+        assert!(config.network().timeout().is_none());
+    }
+
+    #[test]
+    fn it_parses_network_settings() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [network]
+            proxy = "http://proxy.example.com:3128"
+            no_proxy = ["localhost", ".example.com"]
+            ca_certificates = ["/etc/ssl/certs/corporate-ca.pem"]
+            timeout = 30
+            connect_timeout = 10
+        "#})
+        .unwrap();
+        let network = config.network();
+        assert_eq!(network.proxy(), Some("http://proxy.example.com:3128"));
+        assert_eq!(network.no_proxy(), ["localhost", ".example.com"]);
+        assert_eq!(
+            network.ca_certificates(),
+            [PathBuf::from("/etc/ssl/certs/corporate-ca.pem")]
+        );
+        assert_eq!(network.timeout(), Some(Duration::from_secs(30)));
+        assert_eq!(network.connect_timeout(), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn it_lists_settings_that_cannot_be_exported() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [network]
+            proxy = "http://proxy.example.com:3128"
+            ca_certificates = ["/etc/ssl/certs/corporate-ca.pem"]
+            connect_timeout = 10
+        "#})
+        .unwrap();
+        assert_eq!(
+            config.network().unexported_settings(),
+            ["ca_certificates", "connect_timeout"]
+        );
+    }
+
+    #[test]
+    fn it_can_export_proxy_settings() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [network]
+            proxy = "http://proxy.example.com:3128"
+            no_proxy = ["localhost"]
+        "#})
+        .unwrap();
+        assert!(config.network().unexported_settings().is_empty());
+    }
+
+    #[test]
+    fn it_creates_a_client_with_network_settings() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [network]
+            proxy = "http://proxy.example.com:3128"
+            no_proxy = ["localhost"]
+            timeout = 30
+        "#})
+        .unwrap();
+        assert!(config.network().client("usaidwat").is_ok());
+    }
+
+    #[test]
+    fn it_fails_to_create_a_client_with_missing_certificates() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [network]
+            ca_certificates = ["tests/data/doesnotexist.pem"]
+        "#})
+        .unwrap();
+        assert!(config.network().client("usaidwat").is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_files() {
+        let config = ConfigFile::parse("[reddit");
+        assert!(config.is_err(), "should be Err, was {config:?}");
+    }
+}
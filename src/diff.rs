@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Comparing a Redditor's current activity with an earlier snapshot.
+//!
+//! A [`Diff`] compares the comments and posts retrieved from Reddit with
+//! the ones saved in the local [archive](crate::archive) the last time the
+//! user was looked up, and reports what was removed, edited, or rescored
+//! in between.
+//!
+//! Reddit only returns a user's most recent activity, so things in the
+//! earlier snapshot that are older than anything retrieved now are not
+//! reported as removed; they have simply aged out of Reddit's listings.
+//! Likewise, if nothing is retrieved at all, nothing is reported, since an
+//! empty listing says nothing about what happened to the earlier things.
+//!
+//! Things reported as removed should be
+//! [marked as such](crate::archive::Archive::mark_removed) in the archive,
+//! so that later snapshots leave them out and they are only reported once.
+
+use crate::reddit::Redditor;
+use crate::reddit::thing::{Comment, HasBody, HasSubreddit, Removable, Submission};
+use crate::text;
+use horologe::{DateTime, Utc, age::HasAge};
+use similar::{ChangeTag, TextDiff};
+use std::collections::HashMap;
+
+/// A comment or post that can be compared between snapshots.
+pub trait Diffable: HasAge + HasBody + HasSubreddit + Removable {
+    /// The thing's fullname, which identifies it across snapshots.
+    fn fullname(&self) -> &str;
+
+    /// The title of the thing, or of the post a comment belongs to.
+    fn title(&self) -> String;
+
+    /// The full URL at which the thing can be retrieved.
+    fn permalink(&self) -> String;
+
+    /// The thing's total score.
+    fn score(&self) -> i64;
+
+    /// The time the thing was last edited, if it has been edited.
+    fn edited(&self) -> Option<DateTime<Utc>>;
+}
+
+impl Diffable for Comment {
+    fn fullname(&self) -> &str {
+        Comment::fullname(self)
+    }
+
+    fn title(&self) -> String {
+        self.link_title()
+    }
+
+    fn permalink(&self) -> String {
+        Comment::permalink(self)
+    }
+
+    fn score(&self) -> i64 {
+        Comment::score(self)
+    }
+
+    fn edited(&self) -> Option<DateTime<Utc>> {
+        Comment::edited(self)
+    }
+}
+
+impl Diffable for Submission {
+    fn fullname(&self) -> &str {
+        Submission::fullname(self)
+    }
+
+    fn title(&self) -> String {
+        Submission::title(self)
+    }
+
+    fn permalink(&self) -> String {
+        Submission::permalink(self)
+    }
+
+    fn score(&self) -> i64 {
+        Submission::score(self)
+    }
+
+    fn edited(&self) -> Option<DateTime<Utc>> {
+        Submission::edited(self)
+    }
+}
+
+/// A difference between the earlier and current versions of a thing.
+#[derive(Debug)]
+pub enum Change<'a, T> {
+    /// The thing was deleted or removed since the earlier snapshot.
+    Removed(&'a T),
+
+    /// The thing's body was edited.
+    Edited {
+        /// The thing as it was in the earlier snapshot.
+        before: &'a T,
+
+        /// The thing as it is now.
+        after: &'a T,
+    },
+
+    /// The thing's score changed.
+    Rescored {
+        /// The thing as it was in the earlier snapshot.
+        before: &'a T,
+
+        /// The thing as it is now.
+        after: &'a T,
+    },
+}
+
+/// A line in the difference between two versions of a thing's body.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Line {
+    /// A line that appears in both versions.
+    Unchanged(String),
+
+    /// A line that only appears in the earlier version.
+    Deleted(String),
+
+    /// A line that only appears in the current version.
+    Inserted(String),
+}
+
+impl<'a, T: Diffable> Change<'a, T> {
+    /// The fullname of the thing, if it was removed.
+    fn removed(&self) -> Option<&'a str> {
+        match self {
+            Change::Removed(thing) => Some(Diffable::fullname(*thing)),
+            _ => None,
+        }
+    }
+
+    /// The line-by-line difference between the earlier and current bodies
+    /// of an edited thing, or an empty list for other changes.
+    pub fn lines(&self) -> Vec<Line> {
+        match self {
+            Change::Edited { before, after } => {
+                let before = text::convert_html_entities(before.markdown_body());
+                let after = text::convert_html_entities(after.markdown_body());
+                TextDiff::from_lines(&before, &after)
+                    .iter_all_changes()
+                    .map(|change| {
+                        let line = change.value().trim_end_matches('\n').to_string();
+                        match change.tag() {
+                            ChangeTag::Equal => Line::Unchanged(line),
+                            ChangeTag::Delete => Line::Deleted(line),
+                            ChangeTag::Insert => Line::Inserted(line),
+                        }
+                    })
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// The differences between a Redditor's activity in an earlier snapshot
+/// and their current activity.
+#[derive(Debug)]
+pub struct Diff<'a> {
+    comments: Vec<Change<'a, Comment>>,
+    submissions: Vec<Change<'a, Submission>>,
+}
+
+impl<'a> Diff<'a> {
+    /// Compares the `previous` snapshot of a Redditor with the `current` one.
+    pub fn between(previous: &'a Redditor, current: &'a Redditor) -> Self {
+        Self {
+            comments: changes(previous.comments(), current.comments()),
+            submissions: changes(previous.submissions(), current.submissions()),
+        }
+    }
+
+    /// Changes to the Redditor's comments.
+    pub fn comments(&self) -> impl Iterator<Item = &Change<'a, Comment>> {
+        self.comments.iter()
+    }
+
+    /// Changes to the Redditor's posts.
+    pub fn submissions(&self) -> impl Iterator<Item = &Change<'a, Submission>> {
+        self.submissions.iter()
+    }
+
+    /// The fullnames of the comments and posts that were removed.
+    pub fn removed(&self) -> impl Iterator<Item = &str> {
+        let comments = self.comments.iter().filter_map(Change::removed);
+        let submissions = self.submissions.iter().filter_map(Change::removed);
+        comments.chain(submissions)
+    }
+
+    /// True if nothing changed between the snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.comments.is_empty() && self.submissions.is_empty()
+    }
+}
+
+fn changes<'a, T: Diffable>(
+    previous: impl Iterator<Item = &'a T>,
+    current: impl Iterator<Item = &'a T>,
+) -> Vec<Change<'a, T>> {
+    let current: HashMap<_, _> = current.map(|thing| (thing.fullname(), thing)).collect();
+    let Some(oldest) = current.values().map(|thing| thing.created_utc()).min() else {
+        return vec![];
+    };
+
+    previous
+        .flat_map(|before| match current.get(before.fullname()) {
+            None if before.created_utc() >= oldest => vec![Change::Removed(before)],
+            None => vec![],
+            Some(after) if after.is_removed() && !before.is_removed() => {
+                vec![Change::Removed(before)]
+            }
+            Some(after) => {
+                let mut changes = vec![];
+                // Reddit's edit time also catches edits that leave the text
+                // looking the same, like changes to whitespace or entities.
+                if after.markdown_body() != before.markdown_body()
+                    || after.edited() > before.edited()
+                {
+                    changes.push(Change::Edited { before, after });
+                }
+                if after.score() != before.score() {
+                    changes.push(Change::Rescored { before, after });
+                }
+                changes
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::load_data_with;
+    use serde_json::Value;
+
+    fn comments(edit: impl FnOnce(&mut Vec<Value>)) -> Vec<Comment> {
+        let data = load_data_with("comments_mipadi", edit);
+        Comment::parse_page(&data).unwrap().into_items()
+    }
+
+    #[test]
+    fn it_finds_no_changes_in_identical_snapshots() {
+        let previous = comments(|_| {});
+        let current = comments(|_| {});
+        assert!(changes(previous.iter(), current.iter()).is_empty());
+    }
+
+    #[test]
+    fn it_finds_removed_things() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| {
+            comments.remove(3);
+        });
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Removed(thing) if thing.fullname() == previous[3].fullname()),
+            "changes were {changes:?}"
+        );
+    }
+
+    #[test]
+    fn it_finds_things_replaced_by_a_placeholder() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| {
+            comments[3]["body"] = Value::from("[removed]");
+        });
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Removed(_)),
+            "changes were {changes:?}"
+        );
+    }
+
+    #[test]
+    fn it_finds_posts_removed_by_moderators() {
+        let posts = |edit: fn(&mut Vec<Value>)| {
+            let data = load_data_with("submitted_mipadi", edit);
+            Submission::parse_page(&data).unwrap().into_items()
+        };
+        let previous = posts(|_| {});
+        let current = posts(|posts| posts[0]["removed_by_category"] = "moderator".into());
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Removed(thing) if thing.fullname() == previous[0].fullname()),
+            "changes were {changes:?}"
+        );
+    }
+
+    #[test]
+    fn it_finds_no_changes_if_nothing_is_retrieved() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| comments.clear());
+        assert!(changes(previous.iter(), current.iter()).is_empty());
+    }
+
+    #[test]
+    fn it_ignores_things_older_than_the_current_snapshot() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| {
+            comments.pop();
+        });
+        assert!(changes(previous.iter(), current.iter()).is_empty());
+    }
+
+    #[test]
+    fn it_finds_edited_things() {
+        let previous = comments(|comments| {
+            comments[3]["body"] = Value::from("First line\nSecond line");
+        });
+        let current = comments(|comments| {
+            comments[3]["body"] = Value::from("First line\nSecond line, edited");
+            comments[3]["edited"] = Value::from(1745000000.0);
+        });
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Edited { after, .. } if after.edited().is_some()),
+            "changes were {changes:?}"
+        );
+        assert_eq!(
+            changes[0].lines(),
+            vec![
+                Line::Unchanged(String::from("First line")),
+                Line::Deleted(String::from("Second line")),
+                Line::Inserted(String::from("Second line, edited")),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_finds_things_edited_without_changing_their_text() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| {
+            comments[3]["edited"] = Value::from(1745000000.0);
+        });
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Edited { after, .. } if after.edited().is_some()),
+            "changes were {changes:?}"
+        );
+        assert!(
+            changes[0]
+                .lines()
+                .iter()
+                .all(|line| matches!(line, Line::Unchanged(_)))
+        );
+    }
+
+    #[test]
+    fn it_finds_rescored_things() {
+        let previous = comments(|_| {});
+        let current = comments(|comments| {
+            comments[3]["score"] = Value::from(42);
+        });
+        let changes = changes(previous.iter(), current.iter());
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(changes[0], Change::Rescored { after, .. } if after.score() == 42),
+            "changes were {changes:?}"
+        );
+        assert!(changes[0].lines().is_empty());
+    }
+}
@@ -0,0 +1,440 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Reading Redditors' history from Pushshift-style data dumps.
+//!
+//! Archives such as Pushshift and Arctic Shift publish every comment and
+//! submission posted to Reddit as newline-delimited JSON (NDJSON), one file
+//! of comments (`RC_2025-04.zst`) and one of submissions (`RS_2025-04.zst`)
+//! per month. Each line is a single thing in the same form that the Reddit
+//! API returns. A [`Dump`] streams through those files, which may be
+//! compressed with Zstandard, and keeps the things posted by one author.
+//!
+//! Records span the whole history of Reddit, and older or partial records
+//! lack fields that the API always returns now, so missing fields are
+//! filled in where possible and records that are still unusable are
+//! skipped.
+
+use crate::reddit::Redditor;
+use crate::reddit::thing::{About, Comment, Submission, User};
+use anyhow::Context;
+use horologe::{DateTime, Utc, age::HasAge};
+use itertools::Itertools;
+use log::{debug, warn};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// The largest window size, as a power of 2, used to compress dumps.
+///
+/// Pushshift compresses its dumps with a 2 GB window, which is larger than
+/// Zstandard decoders accept by default.
+const WINDOW_LOG_MAX: u32 = 31;
+
+/// Just enough of a record to tell who posted it, without parsing the rest.
+#[derive(Deserialize)]
+struct Author<'a> {
+    #[serde(borrow, default)]
+    author: Option<Cow<'a, str>>,
+}
+
+/// Comments and submissions from a set of NDJSON dump files.
+///
+/// `Dump` follows a builder pattern, starting with [`Dump::new()`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use usaidwat::dump::Dump;
+/// let user = Dump::new(["RC_2025-04.zst", "RS_2025-04.zst"])
+///     .max_items(1000)
+///     .user("mipadi")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Dump {
+    paths: Vec<PathBuf>,
+    max_items: usize,
+}
+
+impl Dump {
+    /// Reads things from the dump files at `paths`.
+    ///
+    /// Files whose names end in `.zst` are decompressed as they are read.
+    /// If a path is a directory, every file in it is read.
+    pub fn new(paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            paths: paths.into_iter().map(Into::into).collect(),
+            max_items: usize::MAX,
+        }
+    }
+
+    /// Only includes the `max_items` most recent comments and the
+    /// `max_items` most recent submissions.
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self { max_items, ..self }
+    }
+
+    /// Builds the Reddit user with the given `username` from the comments
+    /// and submissions in the dump that they posted.
+    ///
+    /// Dumps do not include karma, so it is always zero. The account's
+    /// creation date is taken from the records if they include it, and
+    /// otherwise is the date of the user's earliest comment or submission.
+    pub fn user(&self, username: &str) -> anyhow::Result<User> {
+        let mut records = Records::default();
+        for path in self.files()? {
+            records
+                .read(&path, username)
+                .with_context(|| format!("could not read {}", path.display()))?;
+        }
+        if records.skipped > 0 {
+            warn!(
+                "Skipped {} records from {username} that could not be read",
+                records.skipped
+            );
+        }
+
+        // Dumps can overlap, so the same thing may appear more than once.
+        let comments: Vec<_> = records
+            .comments
+            .into_iter()
+            .sorted_by_key(|comment| Reverse(comment.created_utc()))
+            .unique_by(|comment| comment.fullname().to_string())
+            .take(self.max_items)
+            .collect();
+        let submissions: Vec<_> = records
+            .submissions
+            .into_iter()
+            .sorted_by_key(|submission| Reverse(submission.created_utc()))
+            .unique_by(|submission| submission.fullname().to_string())
+            .take(self.max_items)
+            .collect();
+        debug!(
+            "Found {} comments and {} submissions from {username}",
+            comments.len(),
+            submissions.len()
+        );
+
+        let created_utc = records
+            .author_created_utc
+            .into_iter()
+            .chain(comments.iter().map(HasAge::created_utc))
+            .chain(submissions.iter().map(HasAge::created_utc))
+            .min()
+            .unwrap_or_else(Utc::now);
+        let about: About = serde_json::from_value(json!({
+            "name": username,
+            "id": "",
+            "created_utc": created_utc.timestamp() as f64,
+            "link_karma": 0,
+            "comment_karma": 0,
+        }))?;
+
+        Ok(User::new(about, comments, submissions))
+    }
+
+    /// Builds the Redditor with the given `username` from the dump.
+    pub fn redditor(&self, username: &str) -> anyhow::Result<Redditor> {
+        Ok(Redditor::from_user(username, self.user(username)?))
+    }
+
+    /// The files to read, with the files in directories listed in order.
+    fn files(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for path in &self.paths {
+            if path.is_dir() {
+                let entries = fs::read_dir(path)
+                    .with_context(|| format!("could not read {}", path.display()))?;
+                let mut entries = entries
+                    .map(|entry| entry.map(|entry| entry.path()))
+                    .filter_ok(|path| path.is_file())
+                    .collect::<io::Result<Vec<_>>>()?;
+                entries.sort();
+                files.extend(entries);
+            } else {
+                files.push(path.clone());
+            }
+        }
+        Ok(files)
+    }
+}
+
+/// The things posted by one author, gathered from dump files.
+#[derive(Default)]
+struct Records {
+    comments: Vec<Comment>,
+    submissions: Vec<Submission>,
+    author_created_utc: Option<DateTime<Utc>>,
+    skipped: usize,
+}
+
+impl Records {
+    /// Reads the things posted by `username` from the file at `path`.
+    fn read(&mut self, path: &Path, username: &str) -> anyhow::Result<()> {
+        debug!("Reading {}", path.display());
+        let file = File::open(path)?;
+        let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "zst") {
+            let mut decoder = zstd::Decoder::new(file)?;
+            decoder.window_log_max(WINDOW_LOG_MAX)?;
+            Box::new(decoder)
+        } else {
+            Box::new(file)
+        };
+
+        for line in BufReader::new(reader).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) if err.kind() == io::ErrorKind::InvalidData => {
+                    self.skipped += 1;
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            self.add(&line, username);
+        }
+        Ok(())
+    }
+
+    /// Adds the record on a single `line` if it was posted by `username`.
+    fn add(&mut self, line: &str, username: &str) {
+        // Most records were posted by someone else, so check the author
+        // before parsing the whole record.
+        let author = match serde_json::from_str::<Author>(line) {
+            Ok(Author {
+                author: Some(author),
+            }) => author,
+            Ok(_) => return,
+            Err(_) if line.trim().is_empty() => return,
+            Err(_) => {
+                // Can't tell whose it is, but a truncated line at the end
+                // of a partial dump shouldn't go unnoticed.
+                if line.contains(username) {
+                    self.skipped += 1;
+                }
+                return;
+            }
+        };
+        if !author.eq_ignore_ascii_case(username) {
+            return;
+        }
+
+        let Ok(Value::Object(mut data)) = serde_json::from_str(line) else {
+            self.skipped += 1;
+            return;
+        };
+        if let Some(created_utc) = timestamp(data.get("author_created_utc")) {
+            let earliest = self.author_created_utc.get_or_insert(created_utc);
+            *earliest = (*earliest).min(created_utc);
+        }
+
+        let added = if data.contains_key("title") {
+            fill_in_submission(&mut data);
+            serde_json::from_value(Value::Object(data))
+                .map(|submission| self.submissions.push(submission))
+        } else {
+            fill_in_comment(&mut data);
+            serde_json::from_value(Value::Object(data)).map(|comment| self.comments.push(comment))
+        };
+        if let Err(err) = added {
+            debug!("Skipping record: {err}");
+            self.skipped += 1;
+        }
+    }
+}
+
+/// Fills in the fields of a comment that older records lack.
+fn fill_in_comment(data: &mut Map<String, Value>) {
+    fill_in_common(data, "t1");
+    if !data.contains_key("parent_id") {
+        let link_id = data_field(data, "link_id");
+        data.insert(String::from("parent_id"), link_id);
+    }
+}
+
+/// Fills in the fields of a submission that older records lack.
+fn fill_in_submission(data: &mut Map<String, Value>) {
+    fill_in_common(data, "t3");
+    let id = string_field(data, "id");
+    let subreddit = string_field(data, "subreddit");
+    let is_self = data.get("is_self").and_then(Value::as_bool) == Some(true);
+    data.entry("permalink")
+        .or_insert_with(|| json!(format!("/r/{subreddit}/comments/{id}/")));
+    data.entry("url").or_insert_with(|| json!(""));
+    data.entry("selftext").or_insert_with(|| json!(""));
+    data.entry("num_comments").or_insert_with(|| json!(0));
+    if !data.contains_key("domain") {
+        let domain = if is_self || string_field(data, "url").is_empty() {
+            format!("self.{subreddit}")
+        } else {
+            reqwest::Url::parse(&string_field(data, "url"))
+                .ok()
+                .and_then(|url| url.host_str().map(String::from))
+                .unwrap_or_default()
+        };
+        data.insert(String::from("domain"), json!(domain));
+    }
+}
+
+/// Fills in the fields that comments and submissions share.
+fn fill_in_common(data: &mut Map<String, Value>, kind: &str) {
+    // Early records store timestamps as strings.
+    if let Some(created_utc) = timestamp(data.get("created_utc")) {
+        data.insert(
+            String::from("created_utc"),
+            json!(created_utc.timestamp() as f64),
+        );
+    }
+    if let Some(Value::String(edited)) = data.get("edited") {
+        let edited = edited
+            .parse::<f64>()
+            .map_or(Value::Bool(false), |e| json!(e));
+        data.insert(String::from("edited"), edited);
+    }
+
+    let id = string_field(data, "id");
+    data.entry("name")
+        .or_insert_with(|| json!(format!("{kind}_{id}")));
+    data.entry("subreddit_id").or_insert_with(|| json!(""));
+    let score = data
+        .get("score")
+        .or_else(|| data.get("ups"))
+        .and_then(Value::as_i64)
+        .unwrap_or_default();
+    data.entry("score").or_insert_with(|| json!(score));
+    data.entry("ups").or_insert_with(|| json!(score));
+    data.entry("downs").or_insert_with(|| json!(0));
+}
+
+/// The value of a string field, or an empty string if it is missing.
+fn string_field(data: &Map<String, Value>, field: &str) -> String {
+    data.get(field)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// A copy of a field, or `null` if it is missing.
+fn data_field(data: &Map<String, Value>, field: &str) -> Value {
+    data.get(field).cloned().unwrap_or(Value::Null)
+}
+
+/// Reads a Unix timestamp stored as either a number or a string.
+fn timestamp(value: Option<&Value>) -> Option<DateTime<Utc>> {
+    let secs = match value? {
+        Value::Number(secs) => secs.as_f64()?,
+        Value::String(secs) => secs.parse().ok()?,
+        _ => return None,
+    };
+    DateTime::from_timestamp(secs as i64, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reddit::thing::HasBody;
+
+    const COMMENTS: &str = "tests/data/dump/RC_2025-04.ndjson";
+    const SUBMISSIONS: &str = "tests/data/dump/RS_2025-04.ndjson";
+
+    fn user() -> User {
+        Dump::new([COMMENTS, SUBMISSIONS]).user("mipadi").unwrap()
+    }
+
+    #[test]
+    fn it_keeps_things_posted_by_the_author() {
+        let user = user();
+        assert_eq!(user.comments().count(), 3);
+        assert_eq!(user.submissions().count(), 2);
+        assert!(user.comments().all(|comment| comment.author() == "mipadi"));
+    }
+
+    #[test]
+    fn it_matches_authors_case_insensitively() {
+        let user = Dump::new([COMMENTS]).user("MiPaDi").unwrap();
+        assert_eq!(user.comments().count(), 3);
+    }
+
+    #[test]
+    fn it_reads_every_file_in_a_directory() {
+        let user = Dump::new(["tests/data/dump"]).user("mipadi").unwrap();
+        assert_eq!(user.comments().count(), 3);
+        assert_eq!(user.submissions().count(), 2);
+    }
+
+    #[test]
+    fn it_sorts_things_newest_first() {
+        let dates: Vec<_> = user().comments().map(|c| c.created_utc()).collect();
+        assert!(dates.is_sorted_by(|a, b| a >= b), "{dates:?}");
+    }
+
+    #[test]
+    fn it_limits_the_number_of_things() {
+        let user = Dump::new([COMMENTS, SUBMISSIONS])
+            .max_items(1)
+            .user("mipadi")
+            .unwrap();
+        assert_eq!(user.comments().count(), 1);
+        assert_eq!(user.submissions().count(), 1);
+    }
+
+    #[test]
+    fn it_ignores_duplicate_records() {
+        let user = Dump::new([COMMENTS, COMMENTS]).user("mipadi").unwrap();
+        assert_eq!(user.comments().count(), 3);
+    }
+
+    #[test]
+    fn it_fills_in_missing_fields() {
+        let user = user();
+        let comment = user.comments().last().unwrap();
+        assert_eq!(comment.fullname(), "t1_c2xl9");
+        assert_eq!(comment.score(), 4);
+        assert_eq!(
+            comment.created_utc(),
+            DateTime::from_timestamp(1134365188, 0).unwrap()
+        );
+        assert_eq!(comment.raw_body(), "Reddit is neat.");
+    }
+
+    #[test]
+    fn it_fills_in_missing_submission_fields() {
+        let user = user();
+        let submission = user.submissions().last().unwrap();
+        assert!(submission.is_self());
+        assert_eq!(
+            submission.permalink(),
+            "https://www.reddit.com/r/reddit.com/comments/1fx9a/"
+        );
+    }
+
+    #[test]
+    fn it_uses_the_author_creation_date() {
+        let user = user();
+        assert_eq!(
+            user.about().created_utc(),
+            DateTime::from_timestamp(1121299200, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_finds_nothing_for_other_authors() {
+        let user = Dump::new([COMMENTS]).user("nobody").unwrap();
+        assert_eq!(user.comments().count(), 0);
+    }
+
+    #[test]
+    fn it_fails_if_a_file_does_not_exist() {
+        assert!(
+            Dump::new(["tests/data/dump/RC_1999-01.zst"])
+                .user("mipadi")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,421 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Importing a Redditor's history from Reddit's data export.
+//!
+//! Reddit only returns a user's most recent comments and posts, but users
+//! can [request a copy of their data][export] that includes their full
+//! history. The copy arrives as a zip file of CSV files. An [`Export`]
+//! reads the comments (`comments.csv`) and posts (`posts.csv`) from that
+//! zip file, or from a directory it has been extracted to, and builds a
+//! [`User`] from them.
+//!
+//! The export does not include scores, karma, or the titles of the posts
+//! that comments belong to, so those are always zero or empty.
+//!
+//! [export]: https://support.reddithelp.com/hc/en-us/articles/360043048352
+
+use crate::reddit::Redditor;
+use crate::reddit::thing::User;
+use anyhow::{Context, anyhow};
+use chrono::NaiveDateTime;
+use horologe::{DateTime, Utc};
+use itertools::Itertools;
+use log::{debug, warn};
+use reqwest::Url;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use zip::ZipArchive;
+
+const COMMENTS_FILE: &str = "comments.csv";
+const POSTS_FILE: &str = "posts.csv";
+
+/// A comment, as it appears in `comments.csv`.
+#[derive(Debug, Deserialize)]
+struct CommentRecord {
+    id: String,
+    #[serde(default)]
+    permalink: String,
+    date: String,
+    subreddit: String,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    parent: String,
+    #[serde(default)]
+    body: String,
+}
+
+impl CommentRecord {
+    /// Converts the comment into the form returned by the Reddit API.
+    fn to_json(&self, username: &str) -> anyhow::Result<(DateTime<Utc>, Value)> {
+        let created_utc = parse_date(&self.date)?;
+        let link_id = post_id(&self.permalink)
+            .or_else(|| post_id(&self.link))
+            .ok_or_else(|| anyhow!("comment {} has no link to its post", self.id))?;
+        let parent_id = match self.parent.as_str() {
+            "" => format!("t3_{link_id}"),
+            parent if parent.starts_with("t1_") || parent.starts_with("t3_") => {
+                String::from(parent)
+            }
+            parent => format!("t1_{parent}"),
+        };
+        let data = json!({
+            "id": self.id,
+            "name": format!("t1_{}", self.id),
+            "author": username,
+            "parent_id": parent_id,
+            "subreddit_id": "",
+            "subreddit": self.subreddit,
+            "link_id": format!("t3_{link_id}"),
+            "created_utc": created_utc.timestamp() as f64,
+            "body": self.body,
+            "ups": 0,
+            "downs": 0,
+            "score": 0,
+        });
+        Ok((created_utc, json!({ "kind": "t1", "data": data })))
+    }
+}
+
+/// A post, as it appears in `posts.csv`.
+#[derive(Debug, Deserialize)]
+struct PostRecord {
+    id: String,
+    permalink: String,
+    date: String,
+    subreddit: String,
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    body: String,
+}
+
+impl PostRecord {
+    /// Converts the post into the form returned by the Reddit API.
+    fn to_json(&self, username: &str) -> anyhow::Result<(DateTime<Utc>, Value)> {
+        let created_utc = parse_date(&self.date)?;
+        let permalink = path(&self.permalink);
+        // Self posts link to themselves.
+        let domain = match Url::parse(&self.url) {
+            Ok(url) if path(&self.url) != permalink => url
+                .host_str()
+                .map(|host| host.trim_start_matches("www.").to_string())
+                .unwrap_or_default(),
+            _ => format!("self.{}", self.subreddit),
+        };
+        let data = json!({
+            "id": self.id,
+            "name": format!("t3_{}", self.id),
+            "permalink": permalink,
+            "author": username,
+            "domain": domain,
+            "subreddit_id": "",
+            "subreddit": self.subreddit,
+            "url": self.url,
+            "title": self.title,
+            "selftext": self.body,
+            "created_utc": created_utc.timestamp() as f64,
+            "num_comments": 0,
+            "ups": 0,
+            "downs": 0,
+            "score": 0,
+        });
+        Ok((created_utc, json!({ "kind": "t3", "data": data })))
+    }
+}
+
+/// A Redditor's comments and posts from Reddit's data export.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn example() -> anyhow::Result<()> {
+/// use usaidwat::export::Export;
+/// let user = Export::open("export_mipadi_20260101.zip")?.user("mipadi")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Export {
+    comments: Vec<CommentRecord>,
+    posts: Vec<PostRecord>,
+    max_items: usize,
+}
+
+impl Export {
+    /// Reads the export at `path`, which may be the zip file downloaded from
+    /// Reddit or a directory it has been extracted to.
+    ///
+    /// Returns an error if the export contains neither comments nor posts.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let (comments, posts) = if path.is_dir() {
+            (
+                read_file(&path.join(COMMENTS_FILE))?,
+                read_file(&path.join(POSTS_FILE))?,
+            )
+        } else {
+            read_zip(path).with_context(|| format!("could not read {}", path.display()))?
+        };
+        if comments.is_none() && posts.is_none() {
+            return Err(anyhow!(
+                "{} does not contain {COMMENTS_FILE} or {POSTS_FILE}",
+                path.display()
+            ));
+        }
+        Self::from_csv(
+            comments.unwrap_or_default().as_bytes(),
+            posts.unwrap_or_default().as_bytes(),
+        )
+    }
+
+    /// Reads an export from the contents of its `comments.csv` and
+    /// `posts.csv` files.
+    pub fn from_csv(comments: impl Read, posts: impl Read) -> anyhow::Result<Self> {
+        let comments = csv::Reader::from_reader(comments)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("could not parse {COMMENTS_FILE}"))?;
+        let posts = csv::Reader::from_reader(posts)
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("could not parse {POSTS_FILE}"))?;
+        Ok(Self {
+            comments,
+            posts,
+            max_items: usize::MAX,
+        })
+    }
+
+    /// Only includes the `max_items` most recent comments and the
+    /// `max_items` most recent posts.
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self { max_items, ..self }
+    }
+
+    /// Builds the Reddit user with the given `username` from the export.
+    ///
+    /// The export does not say when the account was created, so the date
+    /// of the user's earliest comment or post is used instead. Comments and
+    /// posts that cannot be read, such as those with invalid dates, are
+    /// skipped.
+    pub fn user(&self, username: &str) -> anyhow::Result<User> {
+        let (comments, bad_comments): (Vec<_>, Vec<_>) = self
+            .comments
+            .iter()
+            .map(|comment| comment.to_json(username))
+            .partition_result();
+        let (posts, bad_posts): (Vec<_>, Vec<_>) = self
+            .posts
+            .iter()
+            .map(|post| post.to_json(username))
+            .partition_result();
+        let skipped = bad_comments.len() + bad_posts.len();
+        if skipped > 0 {
+            for err in bad_comments.iter().chain(&bad_posts) {
+                debug!("Skipping record: {err:#}");
+            }
+            warn!("Skipped {skipped} records from {username} that could not be read");
+        }
+
+        let created_utc = comments
+            .iter()
+            .chain(&posts)
+            .map(|(created_utc, _)| *created_utc)
+            .min()
+            .unwrap_or_else(Utc::now);
+        let about = json!({
+            "kind": "t2",
+            "data": {
+                "name": username,
+                "id": "",
+                "created_utc": created_utc.timestamp() as f64,
+                "link_karma": 0,
+                "comment_karma": 0,
+            },
+        });
+
+        User::parse(
+            about.to_string(),
+            self.listing(comments),
+            self.listing(posts),
+        )
+    }
+
+    /// Builds the Redditor with the given `username` from the export.
+    pub fn redditor(&self, username: &str) -> anyhow::Result<Redditor> {
+        Ok(Redditor::from_user(username, self.user(username)?))
+    }
+
+    /// Arranges `things` into a single page of a listing, newest first.
+    fn listing(&self, mut things: Vec<(DateTime<Utc>, Value)>) -> String {
+        things.sort_by_key(|(created_utc, _)| std::cmp::Reverse(*created_utc));
+        let children: Vec<_> = things
+            .into_iter()
+            .take(self.max_items)
+            .map(|(_, thing)| thing)
+            .collect();
+        json!({ "kind": "Listing", "data": { "children": children, "after": null } }).to_string()
+    }
+}
+
+/// Reads the file at `path`, or returns `None` if it does not exist.
+fn read_file(path: &Path) -> anyhow::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err).with_context(|| format!("could not read {}", path.display())),
+    }
+}
+
+/// Reads the comments and posts from the zip file at `path`.
+///
+/// The files may be at the top of the zip file or in a directory within it.
+fn read_zip(path: &Path) -> anyhow::Result<(Option<String>, Option<String>)> {
+    let mut archive = ZipArchive::new(File::open(path)?)?;
+    let entries: Vec<String> = archive.file_names().map(String::from).collect();
+    let mut read = |name: &str| -> anyhow::Result<Option<String>> {
+        let Some(entry) = entries
+            .iter()
+            .find(|entry| entry.rsplit('/').next() == Some(name))
+        else {
+            return Ok(None);
+        };
+        let mut contents = String::new();
+        archive.by_name(entry)?.read_to_string(&mut contents)?;
+        Ok(Some(contents))
+    };
+    Ok((read(COMMENTS_FILE)?, read(POSTS_FILE)?))
+}
+
+/// Parses a date from the export, like "2025-04-17 20:44:02 UTC".
+fn parse_date(date: &str) -> anyhow::Result<DateTime<Utc>> {
+    let date = date.trim();
+    NaiveDateTime::parse_from_str(date.trim_end_matches(" UTC"), "%Y-%m-%d %H:%M:%S")
+        .map(|date| date.and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(date).map(|date| date.with_timezone(&Utc)))
+        .with_context(|| format!("invalid date: {date}"))
+}
+
+/// The path of a Reddit URL, starting with the subreddit, like
+/// `/r/rpg/comments/1jszv9q/z/`.
+fn path(url: &str) -> &str {
+    url.find("/r/").map_or(url, |start| &url[start..])
+}
+
+/// The ID of the post in a Reddit URL, like `1jszv9q`.
+fn post_id(url: &str) -> Option<&str> {
+    let mut segments = url.split('/');
+    segments.find(|segment| *segment == "comments")?;
+    segments.next().filter(|id| !id.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reddit::thing::{HasBody, HasSubreddit};
+    use horologe::age::HasAge;
+
+    fn export() -> Export {
+        Export::open("tests/data/export").unwrap()
+    }
+
+    #[test]
+    fn it_reads_an_extracted_export() {
+        let user = export().user("mipadi").unwrap();
+        assert_eq!(user.comments().count(), 3);
+        assert_eq!(user.submissions().count(), 2);
+    }
+
+    #[test]
+    fn it_fails_if_the_export_has_no_comments_or_posts() {
+        assert!(Export::open("tests/data/reddit").is_err());
+    }
+
+    #[test]
+    fn it_skips_things_with_invalid_dates() {
+        let comments = "id,permalink,date,subreddit,link,parent,body\n\
+            mm60of1,https://www.reddit.com/r/rpg/comments/1jszv9q/z/mm60of1/,yesterday,rpg,,,Hi\n\
+            mm60of2,https://www.reddit.com/r/rpg/comments/1jszv9q/z/mm60of2/,2025-04-17 20:44:02 UTC,rpg,,,Hi";
+        let export = Export::from_csv(comments.as_bytes(), "".as_bytes()).unwrap();
+        let user = export.user("mipadi").unwrap();
+        let ids: Vec<_> = user.comments().map(|c| c.fullname().to_string()).collect();
+        assert_eq!(ids, ["t1_mm60of2"]);
+    }
+
+    #[test]
+    fn it_sorts_things_newest_first() {
+        let user = export().user("mipadi").unwrap();
+        let dates: Vec<_> = user.comments().map(|c| c.created_utc()).collect();
+        assert!(dates.is_sorted_by(|a, b| a >= b), "{dates:?}");
+    }
+
+    #[test]
+    fn it_limits_the_number_of_things() {
+        let user = export().max_items(1).user("mipadi").unwrap();
+        assert_eq!(user.comments().count(), 1);
+        assert_eq!(user.submissions().count(), 1);
+        assert_eq!(user.comments().next().unwrap().fullname(), "t1_mnpd3zh");
+    }
+
+    #[test]
+    fn it_uses_the_earliest_thing_as_the_account_creation_date() {
+        let user = export().user("mipadi").unwrap();
+        assert_eq!(
+            user.about().created_utc(),
+            parse_date("2024-11-02 09:15:44 UTC").unwrap()
+        );
+    }
+
+    #[test]
+    fn it_reads_comments() {
+        let user = export().user("mipadi").unwrap();
+        let comment = user.comments().next().unwrap();
+        assert_eq!(comment.author(), "mipadi");
+        assert_eq!(comment.subreddit(), "cyphersystem");
+        assert_eq!(
+            comment.permalink(),
+            "https://www.reddit.com/r/cyphersystem/comments/1k1iixf/z/mnpd3zh"
+        );
+        assert_eq!(comment.parent_comment_id(), None);
+        assert_eq!(
+            comment.raw_body(),
+            "I've used it to generate \"cyphers\", and it works well.\n\nYou just need to edit them."
+        );
+    }
+
+    #[test]
+    fn it_reads_replies() {
+        let user = export().user("mipadi").unwrap();
+        let reply = user.comments().nth(1).unwrap();
+        assert_eq!(reply.parent_comment_id(), Some("t1_mlqtxuw"));
+    }
+
+    #[test]
+    fn it_reads_self_posts() {
+        let user = export().user("mipadi").unwrap();
+        let post = user.submissions().next().unwrap();
+        assert!(post.is_self());
+        assert_eq!(post.title(), "What are your favorite one-shots?");
+        assert_eq!(
+            post.permalink(),
+            "https://www.reddit.com/r/rpg/comments/1gs9yzl/what_are_your_favorite_oneshots/"
+        );
+    }
+
+    #[test]
+    fn it_reads_link_posts() {
+        let user = export().user("mipadi").unwrap();
+        let post = user.submissions().nth(1).unwrap();
+        assert!(!post.is_self());
+        assert_eq!(
+            post.url(),
+            "https://www.monkey-robot.com/archives/2024/11/02/"
+        );
+    }
+}
@@ -3,7 +3,7 @@
 
 //! General-purpose search utilities.
 
-use crate::reddit::thing::HasSubreddit;
+use crate::reddit::thing::{Comment, CommentFlag, HasSubreddit};
 use itertools::Itertools;
 use regex::Regex;
 use std::collections::HashSet;
@@ -103,6 +103,23 @@ where
     }
 }
 
+impl<'a, I> RedditFilter<I>
+where
+    I: Iterator<Item = &'a Comment>,
+{
+    /// Returns all comments that have every one of the given flags.
+    ///
+    /// If `flags` is empty, all comments are returned.
+    pub fn flagged(self, flags: &[CommentFlag]) -> RedditFilter<impl Iterator<Item = &'a Comment>> {
+        let things = self
+            .things
+            .filter(|comment| flags.iter().all(|flag| comment.has_flag(*flag)))
+            .collect::<Vec<_>>()
+            .into_iter();
+        RedditFilter { things }
+    }
+}
+
 /// A set of strings.
 ///
 /// This set can function like a normal set, but it can also store _negated_
@@ -322,6 +339,7 @@ mod tests {
 
     mod reddit_filter {
         use super::super::*;
+        use crate::reddit::thing::User;
 
         #[derive(Debug)]
         struct TestSearchable {
@@ -473,6 +491,28 @@ mod tests {
             let filtered = RedditFilter::new(texts.into_iter()).filter(&filter);
             assert_eq!(filtered.collect().len(), n - x);
         }
+
+        #[test]
+        fn it_returns_comments_with_flags() {
+            let user = User::test();
+            let edited = RedditFilter::new(user.comments()).flagged(&[CommentFlag::Edited]);
+            assert_eq!(edited.collect().len(), 4);
+        }
+
+        #[test]
+        fn it_returns_comments_with_every_flag() {
+            let user = User::test();
+            let flags = [CommentFlag::Edited, CommentFlag::Controversial];
+            let flagged = RedditFilter::new(user.comments()).flagged(&flags);
+            assert_eq!(flagged.collect().len(), 0);
+        }
+
+        #[test]
+        fn it_returns_all_comments_if_there_are_no_flags() {
+            let user = User::test();
+            let flagged = RedditFilter::new(user.comments()).flagged(&[]);
+            assert_eq!(flagged.collect().len(), 100);
+        }
     }
 
     mod string_set {
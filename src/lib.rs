@@ -3,7 +3,12 @@
 
 #![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+pub mod archive;
+pub mod config;
 pub mod count;
+pub mod diff;
+pub mod dump;
+pub mod export;
 pub mod filter;
 pub mod reddit;
 pub mod summary;
@@ -5,36 +5,63 @@ use clap::Parser;
 use hypertyper::HttpError;
 use reqwest::StatusCode;
 use std::process;
+use tokio::runtime::Runtime;
 use usaidwat::cli::{Config, Runner};
+use usaidwat::config::ConfigFile;
+use usaidwat::reddit::account::AccountError;
+use usaidwat::reddit::auth::Credentials;
 
 fn die(error_code: i32, message: &str) {
     eprintln!("{}", message);
     process::exit(error_code);
 }
 
-fn dispatch_err(username: &str, err: &anyhow::Error) {
-    let message = match err.downcast_ref::<HttpError>() {
-        Some(HttpError::Http(StatusCode::NOT_FOUND)) => {
+fn dispatch_err(username: Option<&str>, err: &anyhow::Error) {
+    if let Some(err) = err.downcast_ref::<AccountError>() {
+        return die(err.status().exit_code(), &err.to_string());
+    }
+
+    let message = match (err.downcast_ref::<HttpError>(), username) {
+        (Some(HttpError::Http(StatusCode::NOT_FOUND)), Some(username)) => {
             format!("no such user: {username}")
         }
-        _ => err.to_string(),
+        (Some(HttpError::Http(StatusCode::FORBIDDEN)), _) if Credentials::load().is_none() => {
+            String::from(include_str!("help/reddit.txt").trim_end())
+        }
+        _ => format!("{err:#}"),
     };
     die(67, &message)
 }
 
-#[tokio::main]
-async fn main() {
+fn main() {
     let config = Config::parse();
-    let username = config.username();
     env_logger::builder()
         .filter_level(config.verbosity().into())
         .init();
+
+    // Clients created by other libraries, like the OpenAI client, only
+    // learn about the proxy from the environment. Errors in the
+    // configuration file are reported when a client is created.
+    if let Ok(file) = ConfigFile::load() {
+        // SAFETY: The runtime, and thus any other threads, has not been
+        // started yet.
+        unsafe { file.network().export_proxy() };
+    }
+
+    match Runtime::new() {
+        Ok(runtime) => runtime.block_on(run(config)),
+        Err(err) => die(1, &format!("could not start runtime: {err}")),
+    }
+}
+
+async fn run(config: Config) {
+    let username = config.username();
     match Runner::new(config).await {
         Ok(runner) => {
             if let Err(err) = runner.run().await {
                 die(1, &err)
             }
         }
-        Err(err) => dispatch_err(&username, &err),
+        Err(err) => dispatch_err(username.as_deref(), &err),
     }
 }
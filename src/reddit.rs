@@ -1,11 +1,22 @@
 // SPDX-License-Identifier: Apache-2.0
-// Copyright (C) 2025 Michael Dippery <michael@monkey-robot.com>
+// Copyright (C) 2025-2026 Michael Dippery <michael@monkey-robot.com>
 
 //! Reddit API clients and services for communicating with Reddit over HTTP.
 
+pub mod account;
+pub mod auth;
+pub mod builder;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod login;
+pub mod ratelimit;
+pub mod resource;
 pub mod service;
 pub mod thing;
+pub mod user_agent;
 
+#[doc(inline)]
+pub use builder::RedditorBuilder;
 #[doc(inline)]
 pub use client::Redditor;
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! The status of Reddit accounts.
+//!
+//! Not every account's activity can be retrieved. Besides accounts that do
+//! not exist at all, Reddit suspends accounts, and users can hide their
+//! comments and posts from their public profile. [`Redditor::new`] reports
+//! these cases as an [`AccountError`] so callers can tell them apart.
+//!
+//! [`Redditor::new`]: crate::reddit::Redditor::new
+
+use std::{error, fmt};
+
+/// The status of a Reddit account.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AccountStatus {
+    /// The account exists and its activity can be retrieved.
+    Active,
+
+    /// The account has been suspended by Reddit.
+    Suspended,
+
+    /// The account does not exist.
+    NotFound,
+
+    /// The account exists, but its comments and posts are hidden.
+    HistoryHidden,
+}
+
+impl AccountStatus {
+    /// The exit code the program should use when it cannot retrieve an
+    /// account's activity because of its status.
+    ///
+    /// Exit codes follow the conventions of `sysexits.h`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AccountStatus::Active => 0,
+            AccountStatus::Suspended => 77,
+            AccountStatus::NotFound => 67,
+            AccountStatus::HistoryHidden => 66,
+        }
+    }
+}
+
+/// An error retrieving a Redditor's activity because of the status of
+/// their account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccountError {
+    username: String,
+    status: AccountStatus,
+}
+
+impl AccountError {
+    /// Creates an error for the account with the given `username` and
+    /// `status`.
+    pub fn new(username: impl Into<String>, status: AccountStatus) -> Self {
+        Self {
+            username: username.into(),
+            status,
+        }
+    }
+
+    /// The username of the account.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// The status of the account.
+    pub fn status(&self) -> AccountStatus {
+        self.status
+    }
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let username = &self.username;
+        match self.status {
+            AccountStatus::Active => write!(f, "{username} is active"),
+            AccountStatus::Suspended => write!(f, "{username} has been suspended"),
+            AccountStatus::NotFound => write!(f, "no such user: {username}"),
+            AccountStatus::HistoryHidden => {
+                write!(f, "{username} has hidden their comments and posts")
+            }
+        }
+    }
+}
+
+impl error::Error for AccountError {}
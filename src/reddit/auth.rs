@@ -0,0 +1,660 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! OAuth2 authentication for the Reddit API.
+//!
+//! Reddit no longer allows unauthenticated access to its API, so requests
+//! must carry a bearer token obtained from Reddit's access token endpoint
+//! using the client ID and secret of a Reddit app. See Reddit's
+//! [OAuth2 documentation] for details.
+//!
+//! Tokens are cached on disk in a [`TokenStore`] so that each invocation
+//! of `usaidwat` does not have to request a new one.
+//!
+//! [OAuth2 documentation]: https://github.com/reddit-archive/reddit/wiki/OAuth2
+
+use crate::config::ConfigFile;
+use horologe::{DateTime, Utc};
+use hypertyper::prelude::*;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable holding the Reddit app's client ID.
+pub const CLIENT_ID_VAR: &str = "USAIDWAT_CLIENT_ID";
+
+/// Environment variable holding the Reddit app's client secret.
+pub const CLIENT_SECRET_VAR: &str = "USAIDWAT_CLIENT_SECRET";
+
+/// Environment variable holding the Reddit username for script apps.
+pub const USERNAME_VAR: &str = "USAIDWAT_USERNAME";
+
+/// Environment variable holding the Reddit password for script apps.
+pub const PASSWORD_VAR: &str = "USAIDWAT_PASSWORD";
+
+/// Credentials for a Reddit app.
+///
+/// A client ID is always required. "Script" and "web" apps also have a
+/// client secret, and script apps may additionally supply the username
+/// and password of the Reddit account that owns the app, in which case
+/// tokens are requested with the password grant; otherwise the client
+/// credentials grant is used. "Installed" apps have no secret and use
+/// Reddit's installed client grant, or a refresh token obtained by
+/// [logging in](crate::reddit::login).
+#[derive(Clone)]
+pub struct Credentials {
+    client_id: String,
+    client_secret: String,
+    login: Option<(String, String)>,
+}
+
+impl Credentials {
+    /// Reddit's grant type for installed apps acting without a user.
+    const INSTALLED_CLIENT_GRANT: &'static str = "https://oauth.reddit.com/grants/installed_client";
+
+    /// Creates credentials from a Reddit app's client ID and secret.
+    ///
+    /// `client_secret` should be empty for installed apps.
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+            login: None,
+        }
+    }
+
+    /// Adds the username and password of a script app's owner.
+    pub fn with_password(self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            login: Some((username.into(), password.into())),
+            ..self
+        }
+    }
+
+    /// Reads credentials from the `$USAIDWAT_CLIENT_ID` and
+    /// `$USAIDWAT_CLIENT_SECRET` environment variables, and, if both
+    /// are set, the `$USAIDWAT_USERNAME` and `$USAIDWAT_PASSWORD`
+    /// environment variables.
+    ///
+    /// Returns an `Err` result if the client ID is not set. The client
+    /// secret may be omitted for installed apps.
+    pub fn from_env() -> Result<Self, env::VarError> {
+        let client_secret = env::var(CLIENT_SECRET_VAR).unwrap_or_default();
+        let credentials = Self::new(env::var(CLIENT_ID_VAR)?, client_secret);
+        match (env::var(USERNAME_VAR), env::var(PASSWORD_VAR)) {
+            (Ok(username), Ok(password)) => Ok(credentials.with_password(username, password)),
+            _ => Ok(credentials),
+        }
+    }
+
+    /// Reads credentials from the `[reddit]` section of a configuration file.
+    ///
+    /// Returns `None` if the client ID is not set. The client secret may be
+    /// omitted for installed apps.
+    pub fn from_config(config: &ConfigFile) -> Option<Self> {
+        let reddit = config.reddit();
+        let client_secret = reddit.client_secret().unwrap_or_default();
+        let credentials = Self::new(reddit.client_id()?, client_secret);
+        match (reddit.username(), reddit.password()) {
+            (Some(username), Some(password)) => Some(credentials.with_password(username, password)),
+            _ => Some(credentials),
+        }
+    }
+
+    /// Loads credentials from the environment, falling back to the
+    /// user's configuration file.
+    ///
+    /// Returns `None` if credentials are not available from either source.
+    pub fn load() -> Option<Self> {
+        Self::from_env().ok().or_else(|| match ConfigFile::load() {
+            Ok(config) => Self::from_config(&config),
+            Err(err) => {
+                warn!("Could not load configuration file: {err:#}");
+                None
+            }
+        })
+    }
+
+    /// The Reddit app's client ID.
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// The Reddit username the credentials authenticate as, if any.
+    pub fn username(&self) -> Option<&str> {
+        self.login.as_ref().map(|(username, _)| username.as_str())
+    }
+
+    /// True if the credentials belong to an installed app, which has
+    /// no client secret.
+    pub fn is_installed_app(&self) -> bool {
+        self.client_secret.is_empty()
+    }
+
+    /// Requests a new access token from Reddit's access token endpoint at
+    /// `token_uri`.
+    pub async fn request_token<U>(
+        &self,
+        client: &HttpClient,
+        token_uri: U,
+    ) -> HttpResult<AccessToken>
+    where
+        U: IntoUrl,
+    {
+        let form = match &self.login {
+            Some((username, password)) => vec![
+                ("grant_type", "password"),
+                ("username", username.as_str()),
+                ("password", password.as_str()),
+            ],
+            None if self.is_installed_app() => vec![
+                ("grant_type", Self::INSTALLED_CLIENT_GRANT),
+                ("device_id", "DO_NOT_TRACK_THIS_DEVICE"),
+            ],
+            None => vec![("grant_type", "client_credentials")],
+        };
+        debug!("Requesting access token with {} grant", form[0].1);
+        Ok(self.post_token(client, token_uri, &form).await?.into())
+    }
+
+    /// Requests a new access token using a `refresh_token` obtained by
+    /// [logging in](crate::reddit::login).
+    pub async fn refresh_token<U>(
+        &self,
+        client: &HttpClient,
+        token_uri: U,
+        refresh_token: &str,
+    ) -> HttpResult<AccessToken>
+    where
+        U: IntoUrl,
+    {
+        let form = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ];
+        debug!("Refreshing access token");
+        Ok(self.post_token(client, token_uri, &form).await?.into())
+    }
+
+    /// Exchanges an authorization `code` for an access token and, if
+    /// permanent access was requested, a refresh token.
+    pub(crate) async fn exchange_code<U>(
+        &self,
+        client: &HttpClient,
+        token_uri: U,
+        code: &str,
+        redirect_uri: &str,
+    ) -> HttpResult<(AccessToken, Option<String>)>
+    where
+        U: IntoUrl,
+    {
+        let form = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+        ];
+        let resp = self.post_token(client, token_uri, &form).await?;
+        let refresh_token = resp.refresh_token.clone();
+        Ok((resp.into(), refresh_token))
+    }
+
+    /// Revokes a `token`, which may be either an access token or a refresh
+    /// token, as specified by `token_type_hint`.
+    pub(crate) async fn revoke_token<U>(
+        &self,
+        client: &HttpClient,
+        revoke_uri: U,
+        token: &str,
+        token_type_hint: &str,
+    ) -> HttpResult<()>
+    where
+        U: IntoUrl,
+    {
+        let form = [("token", token), ("token_type_hint", token_type_hint)];
+        let resp = client
+            .post(revoke_uri)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(&form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            Err(HttpError::Http(resp.status()))
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn post_token<U>(
+        &self,
+        client: &HttpClient,
+        token_uri: U,
+        form: &[(&str, &str)],
+    ) -> HttpResult<TokenResponse>
+    where
+        U: IntoUrl,
+    {
+        let resp = client
+            .post(token_uri)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            Err(HttpError::Http(resp.status()))
+        } else {
+            Ok(resp.json().await?)
+        }
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    // Keep secrets out of logs.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("client_id", &self.client_id)
+            .field("username", &self.username())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A bearer token used to authenticate requests to the Reddit API.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct AccessToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    /// Tokens are considered expired this many seconds before Reddit
+    /// actually expires them, so a request is never sent with a token
+    /// that expires in flight.
+    const LEEWAY_SECS: i64 = 60;
+
+    /// Creates a new token that expires at the given time.
+    pub fn new(token: impl Into<String>, expires_at: DateTime<Utc>) -> Self {
+        Self {
+            token: token.into(),
+            expires_at,
+        }
+    }
+
+    /// The token itself, suitable for an `Authorization: bearer` header.
+    pub fn secret(&self) -> &str {
+        &self.token
+    }
+
+    /// The time at which the token expires.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// True if the token has expired, or is about to.
+    pub fn is_expired(&self) -> bool {
+        Utc::now().timestamp() + Self::LEEWAY_SECS >= self.expires_at.timestamp()
+    }
+}
+
+impl std::fmt::Debug for AccessToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Caches access tokens on disk between runs.
+///
+/// Tokens are stored as JSON, along with the client ID of the app that
+/// requested them, so a token is never reused after the user switches to
+/// a different app. If the user has [logged in](crate::reddit::login),
+/// the store also holds the refresh token used to request new access
+/// tokens.
+#[derive(Debug)]
+pub struct TokenStore {
+    path: PathBuf,
+}
+
+impl TokenStore {
+    /// Creates a token store backed by the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates a token store in the default location, which is
+    /// `token.json` in the `usaidwat` subdirectory of the user's data
+    /// directory (usually `~/.local/share/usaidwat/token.json`).
+    ///
+    /// Returns `None` if the user's data directory cannot be determined.
+    pub fn default_location() -> Option<Self> {
+        dirs::data_dir().map(|dir| Self::new(dir.join("usaidwat").join("token.json")))
+    }
+
+    /// The file in which tokens are stored.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the stored access token for the app with the given `client_id`.
+    ///
+    /// Returns `None` if no token is stored, the stored token belongs to a
+    /// different app, or the token cannot be read. Expired tokens are still
+    /// returned; it is up to the caller to check for expiration.
+    pub fn load(&self, client_id: &str) -> Option<AccessToken> {
+        self.read(client_id)?.access_token
+    }
+
+    /// Loads the stored refresh token for the app with the given `client_id`,
+    /// if the user has logged in.
+    pub fn refresh_token(&self, client_id: &str) -> Option<String> {
+        self.read(client_id)?.refresh_token
+    }
+
+    /// Saves the `token` requested by the app with the given `client_id`,
+    /// replacing any access token that is already stored.
+    ///
+    /// A stored refresh token for the same app is kept.
+    pub fn save(&self, client_id: &str, token: &AccessToken) -> io::Result<()> {
+        let refresh_token = self.refresh_token(client_id);
+        self.save_login(client_id, token, refresh_token.as_deref())
+    }
+
+    /// Saves the `token` and `refresh_token` obtained by logging in to the
+    /// app with the given `client_id`, replacing anything already stored.
+    pub fn save_login(
+        &self,
+        client_id: &str,
+        token: &AccessToken,
+        refresh_token: Option<&str>,
+    ) -> io::Result<()> {
+        self.write(&TokenRecord {
+            client_id: client_id.to_string(),
+            access_token: Some(token.clone()),
+            refresh_token: refresh_token.map(str::to_string),
+        })
+    }
+
+    /// Discards the stored access token for the app with the given
+    /// `client_id`, but keeps its refresh token, if any.
+    ///
+    /// Tokens stored for other apps are left alone.
+    pub fn discard_access_token(&self, client_id: &str) -> io::Result<()> {
+        match self.read(client_id) {
+            Some(record) if record.refresh_token.is_some() => self.write(&TokenRecord {
+                access_token: None,
+                ..record
+            }),
+            Some(_) => self.clear(),
+            None => Ok(()),
+        }
+    }
+
+    /// Deletes everything in the store.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn read(&self, client_id: &str) -> Option<TokenRecord> {
+        let data = fs::read_to_string(&self.path).ok()?;
+        match serde_json::from_str::<TokenRecord>(&data) {
+            Ok(record) if record.client_id == client_id => Some(record),
+            Ok(_) => None,
+            Err(err) => {
+                warn!(
+                    "Ignoring unreadable token store {}: {err}",
+                    self.path.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Writes the `record` to the store, which, on Unix, is only readable
+    /// by its owner.
+    fn write(&self, record: &TokenRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let data = serde_json::to_string_pretty(record)?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&self.path)?.write_all(data.as_bytes())
+    }
+}
+
+// Response wrappers
+// --------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: i64,
+    refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TokenRecord {
+    client_id: String,
+    access_token: Option<AccessToken>,
+    refresh_token: Option<String>,
+}
+
+impl From<TokenResponse> for AccessToken {
+    fn from(resp: TokenResponse) -> Self {
+        let expires_at = Utc::now() + chrono::TimeDelta::seconds(resp.expires_in);
+        Self::new(resp.access_token, expires_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn it_has_no_login_by_default() {
+        let credentials = Credentials::new("id", "secret");
+        assert_eq!(credentials.client_id(), "id");
+        assert!(credentials.username().is_none());
+    }
+
+    #[test]
+    fn it_can_have_a_login() {
+        let credentials = Credentials::new("id", "secret").with_password("mipadi", "hunter2");
+        assert_eq!(credentials.username(), Some("mipadi"));
+    }
+
+    #[test]
+    fn it_is_an_installed_app_without_a_secret() {
+        let credentials = Credentials::new("id", "");
+        assert!(credentials.is_installed_app());
+    }
+
+    #[test]
+    fn it_is_not_an_installed_app_with_a_secret() {
+        let credentials = Credentials::new("id", "secret");
+        assert!(!credentials.is_installed_app());
+    }
+
+    #[test]
+    fn it_does_not_leak_secrets_in_debug_output() {
+        let credentials = Credentials::new("id", "secret").with_password("mipadi", "hunter2");
+        let debug = format!("{credentials:?}");
+        assert!(!debug.contains("secret"), "{debug}");
+        assert!(!debug.contains("hunter2"), "{debug}");
+    }
+
+    #[test]
+    fn it_parses_a_token_response() {
+        let resp: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "abc123", "token_type": "bearer", "expires_in": 86400, "scope": "*"}"#,
+        )
+        .unwrap();
+        assert!(resp.refresh_token.is_none());
+        let token = AccessToken::from(resp);
+        assert_eq!(token.secret(), "abc123");
+        assert!(!token.is_expired());
+    }
+
+    #[test]
+    fn it_parses_a_token_response_with_a_refresh_token() {
+        let resp: TokenResponse = serde_json::from_str(
+            r#"{"access_token": "abc123", "token_type": "bearer", "expires_in": 86400, "refresh_token": "xyz789", "scope": "read"}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.refresh_token.as_deref(), Some("xyz789"));
+    }
+
+    #[test]
+    fn it_is_expired_after_its_expiration_date() {
+        let token = AccessToken::new("abc123", Utc::now() - TimeDelta::seconds(1));
+        assert!(token.is_expired());
+    }
+
+    #[test]
+    fn it_is_expired_shortly_before_its_expiration_date() {
+        let token = AccessToken::new("abc123", Utc::now() + TimeDelta::seconds(30));
+        assert!(token.is_expired());
+    }
+
+    mod token_store {
+        use super::super::*;
+        use chrono::TimeDelta;
+        use uuid::Uuid;
+
+        fn temp_store() -> TokenStore {
+            let dir = env::temp_dir().join(format!("usaidwat-{}", Uuid::new_v4()));
+            TokenStore::new(dir.join("token.json"))
+        }
+
+        fn token() -> AccessToken {
+            let expires_at = DateTime::from_timestamp(Utc::now().timestamp() + 3600, 0).unwrap();
+            AccessToken::new("abc123", expires_at)
+        }
+
+        #[test]
+        fn it_returns_nothing_if_no_token_is_stored() {
+            let store = temp_store();
+            assert!(store.load("id").is_none());
+        }
+
+        #[test]
+        fn it_saves_and_loads_a_token() {
+            let store = temp_store();
+            let token = token();
+            store.save("id", &token).unwrap();
+
+            let loaded = store.load("id").expect("token should be stored");
+            assert_eq!(loaded.secret(), token.secret());
+            assert_eq!(loaded.expires_at(), token.expires_at());
+        }
+
+        #[test]
+        fn it_does_not_return_tokens_for_other_apps() {
+            let store = temp_store();
+            store.save("id", &token()).unwrap();
+            assert!(store.load("other id").is_none());
+        }
+
+        #[test]
+        fn it_returns_expired_tokens() {
+            let store = temp_store();
+            let token = AccessToken::new("abc123", Utc::now() - TimeDelta::seconds(10));
+            store.save("id", &token).unwrap();
+
+            let loaded = store.load("id").expect("token should be stored");
+            assert!(loaded.is_expired());
+        }
+
+        #[test]
+        fn it_has_no_refresh_token_unless_logged_in() {
+            let store = temp_store();
+            store.save("id", &token()).unwrap();
+            assert!(store.refresh_token("id").is_none());
+        }
+
+        #[test]
+        fn it_saves_a_login() {
+            let store = temp_store();
+            store.save_login("id", &token(), Some("xyz789")).unwrap();
+            assert!(store.load("id").is_some());
+            assert_eq!(store.refresh_token("id").as_deref(), Some("xyz789"));
+        }
+
+        #[test]
+        fn it_keeps_the_refresh_token_when_saving_a_new_access_token() {
+            let store = temp_store();
+            store.save_login("id", &token(), Some("xyz789")).unwrap();
+            store
+                .save("id", &AccessToken::new("def456", token().expires_at()))
+                .unwrap();
+            assert_eq!(store.load("id").unwrap().secret(), "def456");
+            assert_eq!(store.refresh_token("id").as_deref(), Some("xyz789"));
+        }
+
+        #[test]
+        fn it_discards_an_access_token_but_keeps_the_refresh_token() {
+            let store = temp_store();
+            store.save_login("id", &token(), Some("xyz789")).unwrap();
+            store.discard_access_token("id").unwrap();
+            assert!(store.load("id").is_none());
+            assert_eq!(store.refresh_token("id").as_deref(), Some("xyz789"));
+        }
+
+        #[test]
+        fn it_discards_an_access_token_without_a_refresh_token() {
+            let store = temp_store();
+            store.save("id", &token()).unwrap();
+            store.discard_access_token("id").unwrap();
+            assert!(store.load("id").is_none());
+        }
+
+        #[test]
+        fn it_does_not_discard_tokens_for_other_apps() {
+            let store = temp_store();
+            store.save_login("id", &token(), Some("xyz789")).unwrap();
+            store.discard_access_token("other id").unwrap();
+            assert!(store.load("id").is_some());
+            assert_eq!(store.refresh_token("id").as_deref(), Some("xyz789"));
+        }
+
+        #[test]
+        fn it_clears_a_stored_token() {
+            let store = temp_store();
+            store.save("id", &token()).unwrap();
+            store.clear().unwrap();
+            assert!(store.load("id").is_none());
+        }
+
+        #[test]
+        fn it_clears_an_empty_store() {
+            let store = temp_store();
+            assert!(store.clear().is_ok());
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn it_is_only_readable_by_its_owner() {
+            use std::os::unix::fs::PermissionsExt;
+
+            let store = temp_store();
+            store.save("id", &token()).unwrap();
+            let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+}
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Building Redditors from custom services or existing data.
+//!
+//! [`Redditor::new`] always retrieves a user's activity from Reddit with
+//! the default [`RedditService`]. Applications that use usaidwat as a
+//! library can use a [`RedditorBuilder`] instead to retrieve activity
+//! through their own [`Service`], such as one that is authenticated
+//! differently, cached, or mocked for tests, or to create a Redditor
+//! from JSON responses they have already downloaded.
+
+use crate::reddit::Redditor;
+use crate::reddit::client::ListingOptions;
+use crate::reddit::resource::{Sort, TimeWindow};
+use crate::reddit::service::{RedditService, Service};
+use crate::reddit::thing::User;
+
+/// Builds a [`Redditor`].
+///
+/// `RedditorBuilder` follows a builder pattern, starting with
+/// [`RedditorBuilder::new()`] (or [`Redditor::builder()`]), which retrieves
+/// a user's most recent comments and posts from Reddit using the default
+/// [`RedditService`]. The default service, which reads the user's
+/// credentials and configuration file, is only created when the Redditor
+/// is [built](RedditorBuilder::build()) without a custom service.
+///
+/// # Examples
+///
+/// Retrieve a user's top comments and posts of the past year through a
+/// custom service:
+///
+/// ```no_run
+/// # async fn example() -> anyhow::Result<()> {
+/// use usaidwat::reddit::RedditorBuilder;
+/// use usaidwat::reddit::resource::{Sort, TimeWindow};
+/// use usaidwat::reddit::service::RedditService;
+///
+/// let service = RedditService::default().base_uri("http://localhost:8080");
+/// let redditor = RedditorBuilder::new("mipadi")
+///     .service(service)
+///     .max_items(500)
+///     .sort(Sort::Top)
+///     .time(TimeWindow::Year)
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Create a Redditor from responses that have already been downloaded:
+///
+/// ```no_run
+/// # fn example(about: &str, comments: &str, posts: &str) -> anyhow::Result<()> {
+/// use usaidwat::reddit::RedditorBuilder;
+///
+/// let redditor = RedditorBuilder::new("mipadi").parse(about, comments, posts)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RedditorBuilder<S = RedditService> {
+    username: String,
+    service: Option<S>,
+    options: ListingOptions,
+}
+
+impl RedditorBuilder {
+    /// Starts building a Redditor with the given `username`.
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            service: None,
+            options: ListingOptions::default(),
+        }
+    }
+}
+
+impl<S: Service> RedditorBuilder<S> {
+    /// Retrieves the user's activity with `service`.
+    pub fn service<T: Service>(self, service: T) -> RedditorBuilder<T> {
+        RedditorBuilder {
+            username: self.username,
+            service: Some(service),
+            options: self.options,
+        }
+    }
+
+    /// Retrieves at most `max_items` comments and at most `max_items`
+    /// submissions.
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self {
+            options: self.options.max_items(max_items),
+            ..self
+        }
+    }
+
+    /// Requests `page_limit` comments or submissions per page.
+    pub fn page_limit(self, page_limit: u32) -> Self {
+        Self {
+            options: self.options.page_limit(page_limit),
+            ..self
+        }
+    }
+
+    /// Retrieves comments and submissions in the given order.
+    pub fn sort(self, sort: Sort) -> Self {
+        Self {
+            options: self.options.sort(Some(sort)),
+            ..self
+        }
+    }
+
+    /// Retrieves top or controversial comments and submissions from the
+    /// given time window.
+    pub fn time(self, time: TimeWindow) -> Self {
+        Self {
+            options: self.options.time(Some(time)),
+            ..self
+        }
+    }
+
+    /// Also retrieves up to `context` levels of the comments that each
+    /// comment replied to.
+    pub fn context(self, context: usize) -> Self {
+        Self {
+            options: self.options.context(context),
+            ..self
+        }
+    }
+
+    /// Shows a progress indicator on stderr while retrieving pages.
+    pub fn progress(self, progress: bool) -> Self {
+        Self {
+            options: self.options.progress(progress),
+            ..self
+        }
+    }
+
+    /// Retrieves the user's activity and builds the Redditor.
+    ///
+    /// Returns an [`AccountError`] if the account's activity cannot be
+    /// retrieved, or another error if data cannot be retrieved or parsed.
+    ///
+    /// [`AccountError`]: crate::reddit::account::AccountError
+    pub async fn build(self) -> anyhow::Result<Redditor> {
+        match self.service {
+            Some(service) => Redditor::with_service(self.username, &service, &self.options).await,
+            None => {
+                let service = RedditService::default();
+                Redditor::with_service(self.username, &service, &self.options).await
+            }
+        }
+    }
+
+    /// Builds the Redditor from responses that have already been retrieved
+    /// from the Reddit API, without contacting the service.
+    ///
+    /// `user_data` is the user's profile, and `comment_data` and
+    /// `post_data` are pages of their comments and posts, in the same
+    /// format that [`User::parse`] accepts. Listing options are ignored.
+    pub fn parse<T: AsRef<str>>(
+        self,
+        user_data: T,
+        comment_data: T,
+        post_data: T,
+    ) -> anyhow::Result<Redditor> {
+        let user = User::parse(user_data, comment_data, post_data)?;
+        Ok(Redditor::from_user(self.username, user))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestService, load_data};
+
+    #[test]
+    fn it_does_not_create_the_default_service_until_built() {
+        let builder = RedditorBuilder::new("mipadi").max_items(10);
+        assert!(builder.service.is_none());
+    }
+
+    #[tokio::test]
+    async fn it_builds_a_redditor_with_a_service() {
+        let redditor = RedditorBuilder::new("mipadi")
+            .service(TestService::new("mipadi"))
+            .progress(false)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(redditor.username(), "mipadi");
+        assert_eq!(redditor.comments().count(), 100);
+    }
+
+    #[tokio::test]
+    async fn it_limits_the_number_of_items() {
+        let redditor = Redditor::builder("mipadi")
+            .service(TestService::new("mipadi"))
+            .progress(false)
+            .max_items(10)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(redditor.comments().count(), 10);
+        assert_eq!(redditor.submissions().count(), 10);
+    }
+
+    #[tokio::test]
+    async fn it_reports_errors_from_the_service() {
+        let redditor = RedditorBuilder::new("doesnotexist")
+            .service(TestService::new("404"))
+            .progress(false)
+            .build()
+            .await;
+        assert!(redditor.is_err());
+    }
+
+    #[test]
+    fn it_builds_a_redditor_from_downloaded_data() {
+        let redditor = RedditorBuilder::new("mipadi")
+            .parse(
+                load_data("about_mipadi"),
+                load_data("comments_mipadi"),
+                load_data("submitted_mipadi"),
+            )
+            .unwrap();
+        assert_eq!(redditor.username(), "mipadi");
+        assert_eq!(redditor.link_karma(), 11729);
+        assert_eq!(redditor.comments().count(), 100);
+        assert_eq!(redditor.submissions().count(), 100);
+    }
+
+    #[test]
+    fn it_fails_to_build_a_redditor_from_invalid_data() {
+        let redditor = RedditorBuilder::new("mipadi").parse("{}", "{}", "{}");
+        assert!(redditor.is_err());
+    }
+}
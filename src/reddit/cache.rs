@@ -0,0 +1,374 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Caching Reddit API responses on disk.
+//!
+//! Running several commands for the same user in a row would otherwise
+//! request the same data from Reddit every time. A [`CachedService`] wraps
+//! another [`Service`] and saves the raw JSON it returns for each user and
+//! resource in the user's cache directory (usually `~/.cache/usaidwat`),
+//! reusing it until it is older than the cache's time to live.
+
+use crate::reddit::resource::{ListingParams, Resource};
+use crate::reddit::service::Service;
+use hypertyper::prelude::*;
+use itertools::Itertools;
+use log::{debug, warn};
+use reqwest::StatusCode;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How long responses are cached by default.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// How a [`CachedService`] uses its cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CachePolicy {
+    /// Use cached responses until they expire.
+    #[default]
+    Normal,
+
+    /// Ignore cached responses, but cache new ones.
+    Refresh,
+
+    /// Only use cached responses, even if they have expired, and never
+    /// contact Reddit.
+    Offline,
+}
+
+/// A service that caches the responses of another service on disk.
+///
+/// In [offline](CachePolicy::Offline) mode, requests for resources that
+/// have not been cached fail with a 504 Gateway Timeout error, just like an
+/// HTTP cache asked for a response it does not have.
+#[derive(Debug)]
+pub struct CachedService<S: Service> {
+    service: S,
+    dir: PathBuf,
+    ttl: Duration,
+    policy: CachePolicy,
+}
+
+impl<S: Service> CachedService<S> {
+    /// Creates a service that caches responses from `service` in `dir`.
+    pub fn new(service: S, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            service,
+            dir: dir.into(),
+            ttl: DEFAULT_TTL,
+            policy: CachePolicy::default(),
+        }
+    }
+
+    /// Creates a service that caches responses from `service` in the
+    /// `usaidwat` subdirectory of the user's cache directory.
+    ///
+    /// Returns `None` if the user's cache directory cannot be determined.
+    pub fn in_default_location(service: S) -> Option<Self> {
+        dirs::cache_dir().map(|dir| Self::new(service, dir.join("usaidwat")))
+    }
+
+    /// Reuses cached responses until they are older than `ttl`.
+    pub fn ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    /// Uses the cache according to `policy`.
+    pub fn policy(self, policy: CachePolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// The directory in which responses are cached.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file in which the response for `username`'s `resource` is
+    /// cached.
+    ///
+    /// Each page of a listing, and each way of sorting it, is cached in its
+    /// own file. Things retrieved by ID do not belong to the user, so they
+    /// are not cached, and this returns `None`.
+    fn path(&self, username: &str, resource: &Resource) -> Option<PathBuf> {
+        if let Resource::Info(_) = resource {
+            return None;
+        }
+
+        let mut parts = vec![resource.name().to_string()];
+        if let Some(params) = resource.params() {
+            if let Some(sort) = params.sort_order() {
+                parts.push(sort.to_string());
+            }
+            if let Some(time) = params.time_window() {
+                parts.push(time.to_string());
+            }
+            if params.page_limit() != ListingParams::DEFAULT_LIMIT {
+                parts.push(params.page_limit().to_string());
+            }
+            if let Some(after) = params.cursor() {
+                parts.push(after.to_string());
+            }
+        }
+        let filename = parts.iter().map(|part| sanitize(part)).join(".");
+        let path = self
+            .dir
+            .join(sanitize(&username.to_lowercase()))
+            .join(format!("{filename}.json"));
+        Some(path)
+    }
+
+    fn is_fresh(&self, path: &Path) -> bool {
+        if self.policy == CachePolicy::Offline {
+            return true;
+        }
+
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < self.ttl)
+    }
+
+    fn read(&self, path: &Path) -> Option<String> {
+        if self.policy == CachePolicy::Refresh || !self.is_fresh(path) {
+            return None;
+        }
+        fs::read_to_string(path).ok()
+    }
+
+    fn write(&self, path: &Path, data: &str) {
+        let result = match path.parent() {
+            Some(dir) => fs::create_dir_all(dir).and_then(|_| fs::write(path, data)),
+            None => fs::write(path, data),
+        };
+        if let Err(err) = result {
+            warn!("Could not cache response in {}: {err}", path.display());
+        }
+    }
+}
+
+/// Replaces characters that do not belong in a filename.
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+impl<S: Service + Sync> HttpGet for CachedService<S> {
+    /// Sends a GET request using the underlying service, without caching.
+    async fn get<U>(&self, uri: U) -> HttpResult<String>
+    where
+        U: IntoUrl + Send,
+    {
+        self.service.get(uri).await
+    }
+}
+
+impl<S: Service + Sync> Service for CachedService<S> {
+    async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+        let Some(path) = self.path(username, resource) else {
+            if self.policy == CachePolicy::Offline {
+                return Err(HttpError::Http(StatusCode::GATEWAY_TIMEOUT));
+            }
+            return self.service.get_resource(username, resource).await;
+        };
+
+        if let Some(data) = self.read(&path) {
+            debug!("Using cached response from {}", path.display());
+            return Ok(data);
+        }
+
+        if self.policy == CachePolicy::Offline {
+            debug!("No cached response in {}", path.display());
+            return Err(HttpError::Http(StatusCode::GATEWAY_TIMEOUT));
+        }
+
+        let data = self.service.get_resource(username, resource).await?;
+        self.write(&path, &data);
+        Ok(data)
+    }
+
+    fn is_live(&self) -> bool {
+        self.service.is_live()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reddit::resource::{Sort, TimeWindow};
+    use crate::test_utils::TestService;
+    use std::env;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    struct CountingService<'a> {
+        service: TestService<'a>,
+        count: AtomicUsize,
+    }
+
+    impl<'a> CountingService<'a> {
+        fn new() -> Self {
+            Self {
+                service: TestService::new("mipadi"),
+                count: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl<'a> HttpGet for CountingService<'a> {
+        async fn get<U>(&self, uri: U) -> HttpResult<String>
+        where
+            U: IntoUrl + Send,
+        {
+            self.service.get(uri).await
+        }
+    }
+
+    impl<'a> Service for CountingService<'a> {
+        async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+            self.count.fetch_add(1, Ordering::SeqCst);
+            self.service.get_resource(username, resource).await
+        }
+    }
+
+    fn cached_service<'a>() -> CachedService<CountingService<'a>> {
+        let dir = env::temp_dir().join(format!("usaidwat-{}", Uuid::new_v4()));
+        CachedService::new(CountingService::new(), dir)
+    }
+
+    fn count(service: &CachedService<CountingService>) -> usize {
+        service.service.count.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn it_caches_each_page_in_its_own_file() {
+        let service = cached_service();
+        let first = service.path("Mipadi", &Resource::Comments(ListingParams::default()));
+        let next = service.path(
+            "mipadi",
+            &Resource::Comments(ListingParams::default().after("t1_lomc1at")),
+        );
+        assert_eq!(
+            first,
+            Some(service.dir().join("mipadi").join("comments.json"))
+        );
+        assert_eq!(
+            next,
+            Some(
+                service
+                    .dir()
+                    .join("mipadi")
+                    .join("comments.t1_lomc1at.json")
+            )
+        );
+    }
+
+    #[test]
+    fn it_caches_each_sort_order_in_its_own_file() {
+        let service = cached_service();
+        let params = ListingParams::default()
+            .sort(Sort::Top)
+            .time(TimeWindow::Year);
+        let path = service.path("mipadi", &Resource::Submitted(params));
+        assert_eq!(
+            path,
+            Some(service.dir().join("mipadi").join("submitted.top.year.json"))
+        );
+    }
+
+    #[test]
+    fn it_does_not_cache_things_retrieved_by_id() {
+        let service = cached_service();
+        let path = service.path("mipadi", &Resource::Info(vec![String::from("t1_mlqtxuw")]));
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn it_sanitizes_filenames() {
+        let service = cached_service();
+        let path = service.path("../etc", &Resource::About);
+        assert_eq!(path, Some(service.dir().join("___etc").join("about.json")));
+    }
+
+    #[tokio::test]
+    async fn it_caches_responses() {
+        let service = cached_service();
+        let first = service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        let second = service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(count(&service), 1);
+    }
+
+    #[tokio::test]
+    async fn it_does_not_use_expired_responses() {
+        let service = cached_service().ttl(Duration::ZERO);
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(count(&service), 2);
+    }
+
+    #[tokio::test]
+    async fn it_refreshes_responses() {
+        let service = cached_service().policy(CachePolicy::Refresh);
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(count(&service), 2);
+        assert!(
+            service
+                .path("mipadi", &Resource::About)
+                .is_some_and(|path| path.exists())
+        );
+    }
+
+    #[tokio::test]
+    async fn it_uses_expired_responses_offline() {
+        let service = cached_service().ttl(Duration::ZERO);
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        let service = service.policy(CachePolicy::Offline);
+        service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(count(&service), 1);
+    }
+
+    #[tokio::test]
+    async fn it_returns_an_error_for_uncached_responses_offline() {
+        let service = cached_service().policy(CachePolicy::Offline);
+        let resp = service.get_resource("mipadi", &Resource::About).await;
+        assert!(
+            matches!(resp, Err(HttpError::Http(StatusCode::GATEWAY_TIMEOUT))),
+            "response was {resp:?}"
+        );
+        assert_eq!(count(&service), 0);
+    }
+}
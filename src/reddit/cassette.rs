@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Recording and replaying Reddit API responses.
+//!
+//! A [`CassetteService`] in record mode sends requests to Reddit through a
+//! [`RedditService`] and saves each request's URI, along with the status,
+//! headers, and body of its response, to a "cassette" file when it is
+//! dropped. Only the headers needed to replay a response are saved, so
+//! cookies and other session data are not written to disk. In replay mode,
+//! it serves the recorded responses from the cassette instead, without any
+//! network access, which makes it possible to reproduce a run exactly.
+//!
+//! Cassettes are JSON files. Recorded responses are matched to requests by
+//! their path and query string, so a cassette recorded with one Reddit host
+//! can be replayed against another. Requests that were never recorded fail
+//! with a 504 Gateway Timeout error, just like requests for uncached
+//! responses to an offline [`CachedService`](crate::reddit::cache::CachedService),
+//! and the service remembers them so they can be
+//! [reported](CassetteService::unrecorded).
+
+use crate::reddit::resource::Resource;
+use crate::reddit::service::{RawResponse, RedditService, Service};
+use anyhow::Context;
+use hypertyper::prelude::*;
+use log::{debug, warn};
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+/// The response headers that are saved to a cassette.
+const RECORDED_HEADERS: &[&str] = &[
+    "content-type",
+    "retry-after",
+    "x-ratelimit-remaining",
+    "x-ratelimit-used",
+    "x-ratelimit-reset",
+];
+
+/// A single recorded request and its response.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct Interaction {
+    uri: String,
+    #[serde(flatten)]
+    response: RawResponse,
+}
+
+/// A collection of recorded interactions.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("could not read cassette {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("could not parse cassette {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir)?;
+        }
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(path, data)
+            .with_context(|| format!("could not write cassette {}", path.display()))
+    }
+
+    fn find(&self, uri: &str) -> Option<&RawResponse> {
+        let key = request_key(uri);
+        self.interactions
+            .iter()
+            .find(|interaction| request_key(&interaction.uri) == key)
+            .map(|interaction| &interaction.response)
+    }
+}
+
+/// The path and query string of `uri`, which identify a request regardless
+/// of the host it was sent to.
+fn request_key(uri: &str) -> String {
+    match Url::parse(uri) {
+        Ok(uri) => match uri.query() {
+            Some(query) => format!("{}?{query}", uri.path()),
+            None => uri.path().to_string(),
+        },
+        Err(_) => uri.to_string(),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Mode {
+    Record,
+    Replay,
+}
+
+/// A service that records Reddit API responses to a cassette file, or
+/// replays previously recorded responses.
+#[derive(Debug)]
+pub struct CassetteService {
+    service: RedditService,
+    mode: Mode,
+    path: PathBuf,
+    cassette: Mutex<Cassette>,
+    unrecorded: Mutex<Vec<(String, String)>>,
+}
+
+impl CassetteService {
+    /// Creates a service that sends requests using `service` and records
+    /// them to a new cassette at `path`, replacing any existing cassette.
+    ///
+    /// The cassette is written once the service is dropped.
+    pub fn record(service: RedditService, path: impl Into<PathBuf>) -> Self {
+        Self {
+            service,
+            mode: Mode::Record,
+            path: path.into(),
+            cassette: Mutex::new(Cassette::default()),
+            unrecorded: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Creates a service that replays responses from the cassette at `path`.
+    ///
+    /// Returns an error if the cassette cannot be read.
+    pub fn replay(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let cassette = Cassette::load(&path)?;
+        Ok(Self {
+            service: RedditService::new(None),
+            mode: Mode::Replay,
+            path,
+            cassette: Mutex::new(cassette),
+            unrecorded: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// The location of the cassette file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The URI of the first request for `username`'s data that was not
+    /// recorded in the cassette, if any.
+    ///
+    /// A replayed run that asks for something the cassette does not have
+    /// was most likely recorded with different options, or by an older
+    /// version of `usaidwat`.
+    pub fn unrecorded(&self, username: &str) -> Option<String> {
+        self.unrecorded
+            .lock()
+            .expect("unrecorded requests lock is poisoned")
+            .iter()
+            .find(|(user, _)| user == username)
+            .map(|(_, uri)| uri.clone())
+    }
+
+    fn cassette(&self) -> std::sync::MutexGuard<'_, Cassette> {
+        self.cassette.lock().expect("cassette lock is poisoned")
+    }
+
+    async fn record_response(&self, uri: &str) -> HttpResult<RawResponse> {
+        let response = self.service.fetch(uri).await?;
+        debug!("Recording {} response for {uri}", response.status());
+
+        let mut recorded = response.clone();
+        recorded.retain_headers(RECORDED_HEADERS);
+        self.cassette().interactions.push(Interaction {
+            uri: uri.to_string(),
+            response: recorded,
+        });
+        Ok(response)
+    }
+
+    fn replay_response(&self, uri: &str) -> HttpResult<RawResponse> {
+        match self.cassette().find(uri) {
+            Some(response) => {
+                debug!("Replaying {} response for {uri}", response.status());
+                Ok(response.clone())
+            }
+            None => {
+                warn!("No recorded response for {uri} in {}", self.path.display());
+                Err(HttpError::Http(StatusCode::GATEWAY_TIMEOUT))
+            }
+        }
+    }
+}
+
+impl Drop for CassetteService {
+    fn drop(&mut self) {
+        if self.mode != Mode::Record {
+            return;
+        }
+        let cassette = self
+            .cassette
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Err(err) = cassette.save(&self.path) {
+            warn!("{err:#}");
+        }
+    }
+}
+
+impl HttpGet for CassetteService {
+    async fn get<U>(&self, uri: U) -> HttpResult<String>
+    where
+        U: IntoUrl + Send,
+    {
+        let uri = uri.as_str();
+        let response = match self.mode {
+            Mode::Record => self.record_response(uri).await?,
+            Mode::Replay => self.replay_response(uri)?,
+        };
+        response.into_body()
+    }
+}
+
+impl Service for CassetteService {
+    async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+        let uri = self.service.uri(username, resource);
+        if self.mode == Mode::Replay && self.cassette().find(&uri).is_none() {
+            self.unrecorded
+                .lock()
+                .expect("unrecorded requests lock is poisoned")
+                .push((username.to_string(), uri.clone()));
+        }
+        self.get(&uri).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reddit::resource::ListingParams;
+    use indoc::indoc;
+    use std::env;
+    use uuid::Uuid;
+
+    const CASSETTE: &str = indoc! {r#"
+        {
+          "interactions": [
+            {
+              "uri": "https://oauth.reddit.com/user/mipadi/about.json",
+              "status": 200,
+              "headers": {
+                "content-type": "application/json; charset=UTF-8"
+              },
+              "body": "{\"kind\": \"t2\"}"
+            },
+            {
+              "uri": "https://oauth.reddit.com/user/doesnotexist/about.json",
+              "status": 404,
+              "headers": {
+                "content-type": "application/json; charset=UTF-8"
+              },
+              "body": "{\"message\": \"Not Found\", \"error\": 404}"
+            }
+          ]
+        }
+    "#};
+
+    fn temp_path() -> PathBuf {
+        env::temp_dir().join(format!("usaidwat-{}.json", Uuid::new_v4()))
+    }
+
+    fn replay_service() -> CassetteService {
+        let path = temp_path();
+        fs::write(&path, CASSETTE).unwrap();
+        CassetteService::replay(path).unwrap()
+    }
+
+    #[test]
+    fn it_identifies_requests_by_path_and_query() {
+        assert_eq!(
+            request_key("https://oauth.reddit.com/user/mipadi/comments.json?limit=100"),
+            request_key("https://www.reddit.com/user/mipadi/comments.json?limit=100")
+        );
+        assert_ne!(
+            request_key("https://oauth.reddit.com/user/mipadi/comments.json?limit=100"),
+            request_key("https://oauth.reddit.com/user/mipadi/comments.json?limit=100&after=t1_x")
+        );
+    }
+
+    #[tokio::test]
+    async fn it_replays_recorded_responses() {
+        let service = replay_service();
+        let resp = service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(resp, r#"{"kind": "t2"}"#);
+    }
+
+    #[tokio::test]
+    async fn it_replays_recorded_errors() {
+        let service = replay_service();
+        let resp = service.get_resource("doesnotexist", &Resource::About).await;
+        assert!(
+            matches!(resp, Err(HttpError::Http(StatusCode::NOT_FOUND))),
+            "response was {resp:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_returns_an_error_for_unrecorded_requests() {
+        let service = replay_service();
+        let resp = service
+            .get_resource("mipadi", &Resource::Comments(ListingParams::default()))
+            .await;
+        assert!(
+            matches!(resp, Err(HttpError::Http(StatusCode::GATEWAY_TIMEOUT))),
+            "response was {resp:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn it_remembers_unrecorded_requests() {
+        let service = replay_service();
+        let resource = Resource::Comments(ListingParams::default());
+        let _ = service.get_resource("mipadi", &Resource::About).await;
+        let _ = service.get_resource("mipadi", &resource).await;
+        assert_eq!(
+            service.unrecorded("mipadi"),
+            Some(service.service.uri("mipadi", &resource))
+        );
+        assert_eq!(service.unrecorded("doesnotexist"), None);
+    }
+
+    #[test]
+    fn it_returns_an_error_for_missing_cassettes() {
+        let service = CassetteService::replay(temp_path());
+        assert!(service.is_err(), "should be Err, was {service:?}");
+    }
+
+    #[test]
+    fn it_saves_and_loads_cassettes() {
+        let path = temp_path();
+        let cassette: Cassette = serde_json::from_str(CASSETTE).unwrap();
+        cassette.save(&path).unwrap();
+        let loaded = Cassette::load(&path).unwrap();
+        assert_eq!(loaded.interactions, cassette.interactions);
+        fs::remove_file(path).unwrap();
+    }
+}
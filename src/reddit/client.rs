@@ -3,49 +3,372 @@
 
 //! Clients for reading data from the Reddit API.
 
+use crate::reddit::account::{AccountError, AccountStatus};
+use crate::reddit::builder::RedditorBuilder;
+use crate::reddit::resource::{ListingParams, Resource, Sort, TimeWindow};
 use crate::reddit::service::{RedditService, Service};
-use crate::reddit::thing::{Comment, Submission, User};
+use crate::reddit::thing::{About, Comment, Listing, Submission, User};
 pub use chrono::Weekday;
 use chrono::{Datelike, Timelike};
 use horologe::{DateTime, Utc, age::HasAge};
-use tokio::try_join;
+use hypertyper::HttpError;
+use itertools::Itertools;
+use log::{debug, warn};
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+use std::sync::Mutex;
+use tokio::join;
+
+/// The most things Reddit will return for a single request by ID.
+const INFO_BATCH_SIZE: usize = 100;
+
+/// Options controlling how much of a Redditor's history is retrieved.
+///
+/// Reddit returns comments and submissions 100 at a time, and will not
+/// return more than about 1,000 of either, no matter how many pages are
+/// requested.
+#[derive(Debug)]
+pub struct ListingOptions {
+    max_items: usize,
+    progress: bool,
+    sort: Option<Sort>,
+    time: Option<TimeWindow>,
+    page_limit: u32,
+    context: usize,
+}
+
+impl Default for ListingOptions {
+    /// Retrieves the most recent 100 comments and submissions, showing
+    /// progress if stderr is a terminal.
+    fn default() -> Self {
+        Self {
+            max_items: Self::DEFAULT_MAX_ITEMS,
+            progress: io::stderr().is_terminal(),
+            sort: None,
+            time: None,
+            page_limit: ListingParams::DEFAULT_LIMIT,
+            context: 0,
+        }
+    }
+}
+
+impl ListingOptions {
+    /// The number of comments and submissions retrieved by default.
+    pub const DEFAULT_MAX_ITEMS: usize = 100;
+
+    /// Retrieve at most `max_items` comments and at most `max_items`
+    /// submissions.
+    pub fn max_items(self, max_items: usize) -> Self {
+        Self { max_items, ..self }
+    }
+
+    /// Show a progress indicator on stderr while retrieving pages.
+    pub fn progress(self, progress: bool) -> Self {
+        Self { progress, ..self }
+    }
+
+    /// Retrieve comments and submissions in the given order, or in
+    /// Reddit's default order (newest first) if `None`.
+    pub fn sort(self, sort: Option<Sort>) -> Self {
+        Self { sort, ..self }
+    }
+
+    /// Retrieve top or controversial comments and submissions from the
+    /// given time window, or from Reddit's default window if `None`.
+    pub fn time(self, time: Option<TimeWindow>) -> Self {
+        Self { time, ..self }
+    }
+
+    /// Request `page_limit` comments or submissions per page.
+    pub fn page_limit(self, page_limit: u32) -> Self {
+        Self { page_limit, ..self }
+    }
+
+    /// Also retrieve up to `context` levels of the comments that each
+    /// comment replied to.
+    pub fn context(self, context: usize) -> Self {
+        Self { context, ..self }
+    }
+
+    /// True if listings are retrieved in Reddit's default order (newest
+    /// first, from all time), so that an empty listing means the user has
+    /// no visible activity at all.
+    fn lists_everything(&self) -> bool {
+        matches!(self.sort, None | Some(Sort::New))
+            && matches!(self.time, None | Some(TimeWindow::All))
+    }
+
+    /// The parameters for the first page of a listing.
+    fn params(&self) -> ListingParams {
+        let params = ListingParams::default().limit(self.page_limit);
+        let params = match self.sort {
+            Some(sort) => params.sort(sort),
+            None => params,
+        };
+        match self.time {
+            Some(time) => params.time(time),
+            None => params,
+        }
+    }
+}
 
 /// Represents a Reddit user.
 #[derive(Debug)]
 pub struct Redditor {
     username: String,
     user: User,
+    context: HashMap<String, Comment>,
+    context_depth: usize,
 }
 
 impl Redditor {
     /// Creates a new client for retrieving information for a Reddit user with
     /// the given `username`. Returns an `Err` result if data cannot be parsed.
+    ///
+    /// If the user's activity cannot be retrieved because their account
+    /// does not exist, has been suspended, or hides its history, the error
+    /// is an [`AccountError`] describing the account's status.
     pub async fn new(username: impl Into<String>) -> anyhow::Result<Self> {
+        Self::with_options(username, ListingOptions::default()).await
+    }
+
+    /// Creates a new client for retrieving information for a Reddit user with
+    /// the given `username`, retrieving as much of their history as allowed
+    /// by `options`. Returns an `Err` result if data cannot be parsed.
+    pub async fn with_options(
+        username: impl Into<String>,
+        options: ListingOptions,
+    ) -> anyhow::Result<Self> {
         let service = RedditService::default();
-        Self::with_service(username, service).await
+        Self::with_service(username, &service, &options).await
+    }
+
+    /// Starts building a client for the Reddit user with the given
+    /// `username`, allowing the service and listing options to be
+    /// customized. See [`RedditorBuilder`] for details.
+    pub fn builder(username: impl Into<String>) -> RedditorBuilder {
+        RedditorBuilder::new(username)
     }
 
     /// Creates a new client for retrieving information for Reddit users.
     ///
     /// `username` should be the Redditor's username. `service` is the
     /// actual service implementation that will be used to retrieve
-    /// information about the Redditor.
+    /// information about the Redditor, and `options` controls how many
+    /// pages of comments and submissions are retrieved.
     ///
-    /// Returns an [`enum@Error`] if data cannot be parsed for the given username.
+    /// Returns an [`AccountError`] if the account's activity cannot be
+    /// retrieved, or another error if data cannot be parsed for the given
+    /// username.
     pub(crate) async fn with_service<T: Service>(
         username: impl Into<String>,
-        service: T,
+        service: &T,
+        options: &ListingOptions,
     ) -> anyhow::Result<Self> {
         let username = username.into();
+        let progress = Progress::new(options.progress);
+
+        let (about, comments, submissions) = join!(
+            service.get_resource(&username, &Resource::About),
+            Self::fetch_listing(
+                service,
+                &username,
+                Resource::Comments,
+                options,
+                &progress,
+                Comment::parse_page
+            ),
+            Self::fetch_listing(
+                service,
+                &username,
+                Resource::Submitted,
+                options,
+                &progress,
+                Submission::parse_page
+            ),
+        );
+        progress.finish();
 
-        let (user_data, comment_data, post_data) = try_join!(
-            service.get_resource(&username, "about"),
-            service.get_resource(&username, "comments"),
-            service.get_resource(&username, "submitted"),
-        )?;
+        // The account's profile tells us whether it exists at all, so check
+        // it before looking at the listings.
+        let about = about.map_err(|err| match err {
+            HttpError::Http(StatusCode::NOT_FOUND) => {
+                AccountError::new(&username, AccountStatus::NotFound).into()
+            }
+            err => anyhow::Error::from(err),
+        })?;
+        if About::is_suspended(&about) {
+            return Err(AccountError::new(&username, AccountStatus::Suspended).into());
+        }
+        let about = About::parse(&about)?;
+
+        let comments = Self::check_listing(&username, comments)?;
+        let submissions = Self::check_listing(&username, submissions)?;
+        if comments.is_empty()
+            && submissions.is_empty()
+            && about.has_karma()
+            && options.lists_everything()
+            && service.is_live()
+        {
+            // Users who have never posted anything have no karma, so a user
+            // with karma but no activity must be hiding it. A narrower
+            // listing, or an archive, may simply have nothing to show.
+            return Err(AccountError::new(&username, AccountStatus::HistoryHidden).into());
+        }
+
+        let user = User::new(about, comments, submissions);
+        let redditor = Self::from_user(username, user);
+        Ok(redditor
+            .retrieve_context(service, options.context, |redditor| {
+                redditor.comments().collect()
+            })
+            .await)
+    }
 
-        let user = User::parse(&user_data, &comment_data, &post_data)?;
-        Ok(Self { username, user })
+    /// Creates a client for a Reddit user whose data has already been
+    /// retrieved and parsed.
+    pub(crate) fn from_user(username: impl Into<String>, user: User) -> Self {
+        Self {
+            username: username.into(),
+            user,
+            context: HashMap::new(),
+            context_depth: 0,
+        }
+    }
+
+    /// Drops the Redditor's comments and submissions that have been
+    /// deleted or removed.
+    pub fn without_removed(self) -> Self {
+        Self {
+            user: self.user.without_removed(),
+            ..self
+        }
+    }
+
+    /// Retrieves up to `depth` levels of the comments that the comments
+    /// chosen by `select` replied to, replacing any context that was
+    /// retrieved before.
+    ///
+    /// Every level of context costs requests, so callers that only show
+    /// some of the Redditor's comments should only choose those.
+    pub(crate) async fn retrieve_context<T: Service>(
+        self,
+        service: &T,
+        depth: usize,
+        select: impl FnOnce(&Redditor) -> Vec<&Comment>,
+    ) -> Self {
+        if depth == 0 {
+            return self;
+        }
+
+        let context = Self::fetch_context(service, &self.username, select(&self), depth)
+            .await
+            .unwrap_or_else(|err| {
+                warn!(
+                    "Could not retrieve context for {}'s comments: {err:#}",
+                    self.username
+                );
+                HashMap::new()
+            });
+        Self {
+            context,
+            context_depth: depth,
+            ..self
+        }
+    }
+
+    /// Retrieves up to `depth` levels of the comments that `comments`
+    /// replied to, keyed by their fullnames.
+    ///
+    /// Each level is retrieved in batches, so this makes one request per
+    /// level for every [`INFO_BATCH_SIZE`] parent comments.
+    async fn fetch_context<T: Service>(
+        service: &T,
+        username: &str,
+        comments: Vec<&Comment>,
+        depth: usize,
+    ) -> anyhow::Result<HashMap<String, Comment>> {
+        let mut context = HashMap::new();
+        let mut parent_ids: Vec<String> = comments
+            .into_iter()
+            .filter_map(Comment::parent_comment_id)
+            .map(String::from)
+            .unique()
+            .collect();
+
+        for level in 1..=depth {
+            parent_ids.retain(|parent_id| !context.contains_key(parent_id));
+            if parent_ids.is_empty() {
+                break;
+            }
+
+            let mut next_ids = Vec::new();
+            for batch in parent_ids.chunks(INFO_BATCH_SIZE) {
+                let resource = Resource::Info(batch.to_vec());
+                let data = service.get_resource(username, &resource).await?;
+                for parent in Comment::parse_page(&data)?.into_items() {
+                    next_ids.extend(parent.parent_comment_id().map(String::from));
+                    context.insert(parent.fullname().to_string(), parent);
+                }
+            }
+            debug!(
+                "Retrieved {} parent comments for {username} (level {level})",
+                context.len()
+            );
+            parent_ids = next_ids.into_iter().unique().collect();
+        }
+
+        Ok(context)
+    }
+
+    /// Reports a listing that Reddit refuses to return as hidden.
+    fn check_listing<R>(username: &str, listing: anyhow::Result<Vec<R>>) -> anyhow::Result<Vec<R>> {
+        listing.map_err(|err| match err.downcast_ref::<HttpError>() {
+            Some(HttpError::Http(StatusCode::FORBIDDEN)) => {
+                AccountError::new(username, AccountStatus::HistoryHidden).into()
+            }
+            _ => err,
+        })
+    }
+
+    /// Retrieves pages of the `listing` resource until Reddit runs out of
+    /// pages or `options.max_items` items have been retrieved.
+    async fn fetch_listing<T: Service, R>(
+        service: &T,
+        username: &str,
+        listing: fn(ListingParams) -> Resource,
+        options: &ListingOptions,
+        progress: &Progress,
+        parse_page: fn(&str) -> anyhow::Result<Listing<R>>,
+    ) -> anyhow::Result<Vec<R>> {
+        let mut items = Vec::new();
+        let mut params = options.params();
+
+        loop {
+            let resource = listing(params.clone());
+            let data = service.get_resource(username, &resource).await?;
+            let page = parse_page(&data)?;
+            let next = page.after().map(String::from);
+            let page = page.into_items();
+            let is_empty = page.is_empty();
+            items.extend(page);
+            debug!(
+                "Retrieved {} {resource} for {username} (next page: {next:?})",
+                items.len()
+            );
+            progress.update(resource.name(), items.len().min(options.max_items));
+
+            match next {
+                Some(next) if !is_empty && items.len() < options.max_items => {
+                    params = params.after(next)
+                }
+                _ => break,
+            }
+        }
+
+        items.truncate(options.max_items);
+        Ok(items)
     }
 
     /// The Redditor's username.
@@ -63,6 +386,11 @@ impl Redditor {
         self.user.about().comment_karma()
     }
 
+    /// Redditor's account details, such as whether it has been verified.
+    pub fn about(&self) -> &About {
+        self.user.about()
+    }
+
     /// Redditor's comments.
     pub fn comments(&self) -> impl Iterator<Item = &Comment> {
         self.user.comments()
@@ -70,13 +398,35 @@ impl Redditor {
 
     /// Redditor's posts (articles and self posts).
     pub fn submissions(&self) -> impl Iterator<Item = &Submission> {
-        // TODO: Filter out removed posts here?
-        // It will result in the number of returned submissions possibly
-        // being less than 100 (for example, the test data's non-removed
-        // posts are 99).
         self.user.submissions()
     }
 
+    /// The comments that `comment` replied to, nearest first, up to the
+    /// number of levels of context that were retrieved.
+    ///
+    /// Ancestors that could not be retrieved, such as when the comment
+    /// replied directly to a post, end the list early.
+    pub fn ancestors(&self, comment: &Comment) -> Vec<&Comment> {
+        let mut ancestors = Vec::new();
+        let mut parent_id = comment.parent_comment_id();
+        while ancestors.len() < self.context_depth
+            && let Some(parent) = parent_id.and_then(|id| self.context.get(id))
+        {
+            ancestors.push(parent);
+            parent_id = parent.parent_comment_id();
+        }
+        ancestors
+    }
+
+    /// The thread of comments leading up to `comment`, including the
+    /// ancestors that were retrieved as context.
+    pub fn thread<'a>(&'a self, comment: &'a Comment) -> Thread<'a> {
+        Thread {
+            comment,
+            ancestors: self.ancestors(comment),
+        }
+    }
+
     /// True if the user has posted at least one comment.
     pub fn has_comments(&self) -> bool {
         self.comments().count() > 0
@@ -101,6 +451,73 @@ impl HasAge for Redditor {
     }
 }
 
+/// A Redditor's comment along with the comments it replied to.
+#[derive(Debug)]
+pub struct Thread<'a> {
+    comment: &'a Comment,
+    ancestors: Vec<&'a Comment>,
+}
+
+impl<'a> Thread<'a> {
+    /// The Redditor's comment.
+    pub fn comment(&self) -> &'a Comment {
+        self.comment
+    }
+
+    /// The comments that the Redditor's comment replied to, oldest first.
+    pub fn ancestors(&self) -> impl Iterator<Item = &'a Comment> {
+        self.ancestors.iter().rev().copied()
+    }
+}
+
+/// Reports the number of items retrieved so far on stderr.
+#[derive(Debug)]
+struct Progress {
+    enabled: bool,
+    counts: Mutex<Vec<(&'static str, usize)>>,
+}
+
+impl Progress {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            counts: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn update(&self, resource: &'static str, count: usize) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut counts = self.counts.lock().expect("progress lock is poisoned");
+        match counts.iter_mut().find(|(r, _)| *r == resource) {
+            Some((_, n)) => *n = count,
+            None => counts.push((resource, count)),
+        }
+        let status = counts
+            .iter()
+            .map(|(resource, count)| match *resource {
+                "submitted" => format!("{count} posts"),
+                _ => format!("{count} {resource}"),
+            })
+            .join(", ");
+        eprint!("\r\x1b[KRetrieved {status}");
+    }
+
+    fn finish(&self) {
+        if self.enabled
+            && !self
+                .counts
+                .lock()
+                .expect("progress lock is poisoned")
+                .is_empty()
+        {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
+
 /// A day of comments, bucketed by hour, which each hour containing the
 /// number of comments for that hour.
 pub type TimelineDay = [u32; 24];
@@ -321,6 +738,185 @@ mod tests {
         }
     }
 
+    mod pagination {
+        use crate::reddit::Redditor;
+        use crate::reddit::client::ListingOptions;
+        use crate::reddit::thing::{Comment, Submission};
+        use crate::test_utils::TestService;
+        use itertools::Itertools;
+
+        async fn test_with_max_items(service: &TestService<'_>, max_items: usize) -> Redditor {
+            let options = ListingOptions::default()
+                .max_items(max_items)
+                .progress(false);
+            Redditor::with_service("mipadi", service, &options)
+                .await
+                .unwrap()
+        }
+
+        fn cursors(cursors: &[&str]) -> Vec<Option<String>> {
+            let cursors = cursors.iter().map(|cursor| Some(String::from(*cursor)));
+            [None].into_iter().chain(cursors).collect()
+        }
+
+        #[tokio::test]
+        async fn it_retrieves_additional_pages() {
+            let service = TestService::new("mipadi").pages(3);
+            let client = test_with_max_items(&service, 250).await;
+            assert_eq!(client.comments().count(), 250);
+            assert_eq!(client.submissions().count(), 250);
+            assert_eq!(
+                client.comments().map(Comment::fullname).unique().count(),
+                250
+            );
+            assert_eq!(
+                client
+                    .submissions()
+                    .map(Submission::fullname)
+                    .unique()
+                    .count(),
+                250
+            );
+        }
+
+        #[tokio::test]
+        async fn it_passes_along_the_cursor_of_the_next_page() {
+            let service = TestService::new("mipadi").pages(3);
+            test_with_max_items(&service, 250).await;
+            assert_eq!(
+                service.cursors("comments"),
+                cursors(&["t1_lomc1at", "t1_lomc1at_2"])
+            );
+            assert_eq!(
+                service.cursors("submitted"),
+                cursors(&["t3_2au1ki", "t3_2au1ki_2"])
+            );
+        }
+
+        #[tokio::test]
+        async fn it_retrieves_fewer_items_than_a_page() {
+            let service = TestService::new("mipadi").pages(3);
+            let client = test_with_max_items(&service, 25).await;
+            assert_eq!(client.comments().count(), 25);
+            assert_eq!(client.submissions().count(), 25);
+            assert_eq!(service.cursors("comments"), cursors(&[]));
+        }
+
+        #[tokio::test]
+        async fn it_stops_at_the_last_page() {
+            let service = TestService::new("mipadi").pages(2);
+            let client = test_with_max_items(&service, 1000).await;
+            assert_eq!(client.comments().count(), 200);
+            assert_eq!(client.submissions().count(), 200);
+            assert_eq!(service.cursors("comments"), cursors(&["t1_lomc1at"]));
+            assert_eq!(service.cursors("submitted"), cursors(&["t3_2au1ki"]));
+        }
+
+        #[tokio::test]
+        async fn it_stops_at_an_empty_listing() {
+            let service = TestService::new("empty");
+            let client = test_with_max_items(&service, 1000).await;
+            assert_eq!(client.comments().count(), 0);
+            assert_eq!(client.submissions().count(), 0);
+            assert_eq!(service.cursors("comments"), cursors(&[]));
+        }
+    }
+
+    mod listing_options {
+        use crate::reddit::client::ListingOptions;
+        use crate::reddit::resource::{ListingParams, Sort, TimeWindow};
+
+        #[test]
+        fn it_requests_the_default_order() {
+            let params = ListingOptions::default().params();
+            assert_eq!(params, ListingParams::default());
+        }
+
+        #[test]
+        fn it_requests_a_sort_order_and_time_window() {
+            let params = ListingOptions::default()
+                .sort(Some(Sort::Top))
+                .time(Some(TimeWindow::Year))
+                .params();
+            assert_eq!(params.sort_order(), Some(Sort::Top));
+            assert_eq!(params.time_window(), Some(TimeWindow::Year));
+        }
+
+        #[test]
+        fn it_requests_a_page_limit() {
+            let params = ListingOptions::default().page_limit(25).params();
+            assert_eq!(params.page_limit(), 25);
+        }
+    }
+
+    mod context {
+        use crate::reddit::Redditor;
+        use crate::reddit::client::ListingOptions;
+        use crate::test_utils::TestService;
+
+        async fn test_with_context(context: usize) -> Redditor {
+            let options = ListingOptions::default().progress(false).context(context);
+            Redditor::with_service("mipadi", &TestService::new("mipadi"), &options)
+                .await
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn it_retrieves_parent_comments() {
+            let user = test_with_context(2).await;
+            let comment = user.comments().nth(3).unwrap();
+            let ancestors: Vec<_> = user
+                .ancestors(comment)
+                .into_iter()
+                .map(|ancestor| ancestor.author())
+                .collect();
+            assert_eq!(ancestors, ["ThreadReplier", "ThreadStarter"]);
+        }
+
+        #[tokio::test]
+        async fn it_limits_the_levels_of_context() {
+            let user = test_with_context(1).await;
+            let comment = user.comments().nth(3).unwrap();
+            assert_eq!(user.ancestors(comment).len(), 1);
+        }
+
+        #[tokio::test]
+        async fn it_has_no_context_for_replies_to_posts() {
+            let user = test_with_context(2).await;
+            let comment = user.comments().next().unwrap();
+            assert!(user.ancestors(comment).is_empty());
+        }
+
+        #[tokio::test]
+        async fn it_retrieves_context_for_chosen_comments() {
+            let user = Redditor::test()
+                .await
+                .retrieve_context(&TestService::new("mipadi"), 2, |user| {
+                    user.comments().skip(3).take(1).collect()
+                })
+                .await;
+            let comment = user.comments().nth(3).unwrap();
+            assert_eq!(user.ancestors(comment).len(), 2);
+        }
+
+        #[tokio::test]
+        async fn it_retrieves_no_context_if_no_comments_are_chosen() {
+            let user = Redditor::test()
+                .await
+                .retrieve_context(&TestService::new("mipadi"), 2, |_| vec![])
+                .await;
+            let comment = user.comments().nth(3).unwrap();
+            assert!(user.ancestors(comment).is_empty());
+        }
+
+        #[tokio::test]
+        async fn it_does_not_retrieve_context_by_default() {
+            let user = Redditor::test().await;
+            let comment = user.comments().nth(3).unwrap();
+            assert!(user.ancestors(comment).is_empty());
+        }
+    }
+
     mod invalid_user {
         use crate::reddit::Redditor;
 
@@ -331,6 +927,103 @@ mod tests {
         }
     }
 
+    mod account_status {
+        use crate::reddit::Redditor;
+        use crate::reddit::account::{AccountError, AccountStatus};
+        use crate::reddit::client::ListingOptions;
+        use crate::reddit::resource::{Sort, TimeWindow};
+        use crate::test_utils::TestService;
+
+        async fn status_with_options(
+            username: &str,
+            suffix: &str,
+            options: ListingOptions,
+        ) -> Option<AccountStatus> {
+            Redditor::with_service(username, &TestService::new(suffix), &options)
+                .await
+                .err()
+                .and_then(|err| err.downcast_ref::<AccountError>().map(AccountError::status))
+        }
+
+        async fn status(username: &str, suffix: &str) -> Option<AccountStatus> {
+            let options = ListingOptions::default().progress(false);
+            status_with_options(username, suffix, options).await
+        }
+
+        #[tokio::test]
+        async fn it_detects_suspended_accounts() {
+            let status = status("suspendeduser", "suspended").await;
+            assert_eq!(status, Some(AccountStatus::Suspended));
+        }
+
+        #[tokio::test]
+        async fn it_detects_hidden_history() {
+            let status = status("hiddenuser", "hidden").await;
+            assert_eq!(status, Some(AccountStatus::HistoryHidden));
+        }
+
+        #[tokio::test]
+        async fn it_detects_hidden_history_in_the_default_order() {
+            let options = ListingOptions::default()
+                .progress(false)
+                .sort(Some(Sort::New))
+                .time(Some(TimeWindow::All));
+            let status = status_with_options("hiddenuser", "hidden", options).await;
+            assert_eq!(status, Some(AccountStatus::HistoryHidden));
+        }
+
+        #[tokio::test]
+        async fn it_does_not_mistake_empty_time_windows_for_hidden_history() {
+            let options = ListingOptions::default()
+                .progress(false)
+                .sort(Some(Sort::Top))
+                .time(Some(TimeWindow::Hour));
+            let status = status_with_options("hiddenuser", "hidden", options).await;
+            assert_eq!(status, None);
+        }
+
+        #[tokio::test]
+        async fn it_does_not_mistake_new_accounts_for_hidden_history() {
+            let status = status("testuserpleaseignore", "empty").await;
+            assert_eq!(status, None);
+        }
+    }
+
+    mod removed {
+        use crate::reddit::Redditor;
+        use crate::reddit::thing::{Removable, User};
+        use crate::test_utils::{load_data, load_data_with};
+
+        fn redditor() -> Redditor {
+            let comments = load_data_with("comments_mipadi", |comments| {
+                comments[0]["body"] = "[deleted]".into();
+                comments[1]["body"] = "[removed]".into();
+            });
+            let posts = load_data_with("submitted_mipadi", |posts| {
+                posts[0]["selftext"] = "[removed]".into();
+            });
+            let user = User::parse(load_data("about_mipadi"), comments, posts).unwrap();
+            Redditor::from_user("mipadi", user)
+        }
+
+        #[test]
+        fn it_drops_removed_things() {
+            let redditor = redditor().without_removed();
+            assert_eq!(redditor.comments().count(), 98);
+            // Seven posts in the test data were removed by moderators.
+            assert_eq!(redditor.submissions().count(), 92);
+            assert!(redditor.comments().all(|c| !c.is_removed()));
+            assert!(redditor.submissions().all(|s| !s.is_removed()));
+        }
+
+        #[test]
+        fn it_keeps_removed_things_by_default() {
+            let redditor = redditor();
+            assert_eq!(redditor.comments().count(), 100);
+            assert_eq!(redditor.submissions().count(), 100);
+        }
+    }
+
     mod timeline {
         use crate::reddit::Redditor;
         use chrono::Weekday;
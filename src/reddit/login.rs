@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Logging in to Reddit as an "installed" app.
+//!
+//! Installed apps cannot keep a client secret, so instead of using a
+//! password, the user authorizes the app in their browser. Reddit then
+//! redirects the browser to a temporary HTTP listener on `localhost`,
+//! which receives an authorization code that is exchanged for a
+//! long-lived refresh token. The refresh token is saved in the
+//! [`TokenStore`], where [`RedditService`](crate::reddit::service::RedditService)
+//! uses it to request access tokens on the user's behalf.
+//!
+//! The Reddit app's redirect URI must be set to the
+//! [redirect URI](Login::redirect_uri) used by the listener, which is
+//! `http://localhost:65010/authorize_callback` by default.
+
+use crate::reddit::auth::{Credentials, TokenStore};
+use crate::reddit::service::RedditService;
+use anyhow::{Context, anyhow, bail};
+use hypertyper::prelude::*;
+use log::{debug, warn};
+use reqwest::Url;
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+const CALLBACK_PATH: &str = "/authorize_callback";
+const SCOPES: &str = "identity history read";
+
+/// How long the user has to authorize the app.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How long a connection to the listener has to send its request.
+///
+/// Browsers often open connections they never use, so a connection that
+/// stays quiet must not hold up the others.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An in-progress login.
+///
+/// Create a login by [binding](Login::bind) a local listener, send the user
+/// to the [authorization URI](Login::authorization_uri), then
+/// [finish](Login::finish) the login once they have authorized the app.
+#[derive(Debug)]
+pub struct Login {
+    credentials: Credentials,
+    client: HttpClient,
+    listener: TcpListener,
+    state: String,
+    redirect_uri: String,
+    auth_uri: String,
+}
+
+impl Login {
+    /// The port the local listener binds to by default.
+    pub const DEFAULT_PORT: u16 = 65010;
+
+    /// Starts logging in to the app identified by `credentials`, listening
+    /// for Reddit's redirect on the given `port`.
+    pub async fn bind(credentials: Credentials, port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let port = listener.local_addr()?.port();
+        Ok(Self {
+            credentials,
+            client: RedditService::http_client_or_default(),
+            listener,
+            state: Uuid::new_v4().to_string(),
+            redirect_uri: format!("http://localhost:{port}{CALLBACK_PATH}"),
+            auth_uri: RedditService::default_auth_uri(),
+        })
+    }
+
+    /// Sends the user to `{auth_uri}/api/v1/authorize` and exchanges the
+    /// authorization code at `{auth_uri}/api/v1/access_token` instead of
+    /// Reddit.
+    pub fn auth_uri(self, auth_uri: impl Into<String>) -> Self {
+        Self {
+            auth_uri: auth_uri.into().trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    /// The URI to which Reddit redirects the user's browser after they
+    /// authorize the app. This must match the redirect URI configured for
+    /// the app on Reddit.
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// The URI the user must visit to authorize the app.
+    pub fn authorization_uri(&self) -> Url {
+        Url::parse_with_params(
+            &format!("{}/api/v1/authorize", self.auth_uri),
+            &[
+                ("client_id", self.credentials.client_id()),
+                ("response_type", "code"),
+                ("state", &self.state),
+                ("redirect_uri", &self.redirect_uri),
+                ("duration", "permanent"),
+                ("scope", SCOPES),
+            ],
+        )
+        .expect("authorization URI is valid")
+    }
+
+    /// Waits for Reddit to redirect the user's browser back to the local
+    /// listener, then exchanges the authorization code for a refresh token
+    /// and saves it in the `store`.
+    ///
+    /// Returns an error if the user does not authorize the app within five
+    /// minutes.
+    pub async fn finish(self, store: &TokenStore) -> anyhow::Result<()> {
+        let code = timeout(LOGIN_TIMEOUT, self.wait_for_code(CONNECTION_TIMEOUT))
+            .await
+            .map_err(|_| anyhow!("timed out waiting for authorization"))??;
+
+        let (token, refresh_token) = self
+            .credentials
+            .exchange_code(
+                &self.client,
+                format!("{}/api/v1/access_token", self.auth_uri),
+                &code,
+                &self.redirect_uri,
+            )
+            .await
+            .context("could not exchange authorization code")?;
+        let refresh_token =
+            refresh_token.ok_or_else(|| anyhow!("Reddit did not return a refresh token"))?;
+
+        store
+            .save_login(self.credentials.client_id(), &token, Some(&refresh_token))
+            .with_context(|| format!("could not save token to {}", store.path().display()))
+    }
+
+    /// Accepts connections until one of them delivers Reddit's redirect,
+    /// returning the authorization code it carries.
+    ///
+    /// Connections that fail, or that send no request within
+    /// `connection_timeout`, are skipped.
+    async fn wait_for_code(&self, connection_timeout: Duration) -> anyhow::Result<String> {
+        loop {
+            let (stream, addr) = self.listener.accept().await?;
+            debug!("Accepted connection from {addr}");
+            match timeout(connection_timeout, self.handle(stream)).await {
+                Ok(Ok(Some(result))) => return result,
+                Ok(Ok(None)) => {}
+                Ok(Err(err)) => warn!("Could not handle connection from {addr}: {err}"),
+                Err(_) => debug!("Connection from {addr} sent no request"),
+            }
+        }
+    }
+
+    /// Reads a single request from the browser and responds to it.
+    ///
+    /// Returns `None` if the request was not for the callback path (browsers
+    /// like to ask for `/favicon.ico`, for example); otherwise returns the
+    /// authorization code, or an error if the user denied access.
+    async fn handle(&self, stream: TcpStream) -> io::Result<Option<anyhow::Result<String>>> {
+        let mut stream = BufReader::new(stream);
+        let mut request_line = String::new();
+        stream.read_line(&mut request_line).await?;
+
+        let result = parse_callback(&request_line, &self.state);
+        let (status, body) = match &result {
+            None => ("404 Not Found", "Not found."),
+            Some(Ok(_)) => (
+                "200 OK",
+                "usaidwat is now logged in to Reddit. You may close this window.",
+            ),
+            Some(Err(_)) => (
+                "400 Bad Request",
+                "usaidwat could not log in to Reddit. Please try again.",
+            ),
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        stream.get_mut().write_all(response.as_bytes()).await?;
+        stream.get_mut().shutdown().await?;
+
+        Ok(result)
+    }
+}
+
+/// Parses the authorization code out of the `request_line` of Reddit's
+/// redirect, verifying that it carries the expected `state`.
+fn parse_callback(request_line: &str, state: &str) -> Option<anyhow::Result<String>> {
+    let path = request_line.split_whitespace().nth(1)?;
+    let uri = Url::parse(&format!("http://localhost{path}")).ok()?;
+    if uri.path() != CALLBACK_PATH {
+        return None;
+    }
+
+    let param = |name: &str| {
+        uri.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    let result = if let Some(err) = param("error") {
+        Err(anyhow!("Reddit denied access: {err}"))
+    } else if param("state").as_deref() != Some(state) {
+        Err(anyhow!("authorization state did not match"))
+    } else {
+        param("code").ok_or_else(|| anyhow!("Reddit did not return an authorization code"))
+    };
+    Some(result)
+}
+
+/// Revokes the refresh token saved by logging in to the app identified by
+/// `credentials` and deletes it from the `store`.
+///
+/// The token is revoked at Reddit's OAuth2 endpoint, or at the one in
+/// `$USAIDWAT_REDDIT_AUTH_URL` if it is set.
+///
+/// Returns an error if the user is not logged in.
+pub async fn logout(credentials: &Credentials, store: &TokenStore) -> anyhow::Result<()> {
+    let Some(refresh_token) = store.refresh_token(credentials.client_id()) else {
+        bail!("not logged in");
+    };
+
+    credentials
+        .revoke_token(
+            &RedditService::http_client_or_default(),
+            format!("{}/api/v1/revoke_token", RedditService::default_auth_uri()),
+            &refresh_token,
+            "refresh_token",
+        )
+        .await
+        .context("could not revoke refresh token")?;
+
+    store
+        .clear()
+        .with_context(|| format!("could not delete {}", store.path().display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    const STATE: &str = "f0e4c2f76c58916ec258f246851bea091d14d4247a2fc3e18694461b1816e13b";
+
+    fn request_line(query: &str) -> String {
+        format!("GET {CALLBACK_PATH}?{query} HTTP/1.1\r\n")
+    }
+
+    #[test]
+    fn it_parses_an_authorization_code() {
+        let line = request_line(&format!("state={STATE}&code=abc123"));
+        let code = parse_callback(&line, STATE).unwrap().unwrap();
+        assert_eq!(code, "abc123");
+    }
+
+    #[test]
+    fn it_rejects_a_mismatched_state() {
+        let line = request_line("state=bogus&code=abc123");
+        let result = parse_callback(&line, STATE).unwrap();
+        assert!(result.is_err(), "should be Err, was {result:?}");
+    }
+
+    #[test]
+    fn it_rejects_a_denied_authorization() {
+        let line = request_line(&format!("state={STATE}&error=access_denied"));
+        let result = parse_callback(&line, STATE).unwrap();
+        assert!(result.is_err(), "should be Err, was {result:?}");
+    }
+
+    #[test]
+    fn it_rejects_a_missing_code() {
+        let line = request_line(&format!("state={STATE}"));
+        let result = parse_callback(&line, STATE).unwrap();
+        assert!(result.is_err(), "should be Err, was {result:?}");
+    }
+
+    #[test]
+    fn it_ignores_other_paths() {
+        let line = "GET /favicon.ico HTTP/1.1\r\n";
+        assert!(parse_callback(line, STATE).is_none());
+    }
+
+    #[test]
+    fn it_ignores_malformed_requests() {
+        assert!(parse_callback("", STATE).is_none());
+    }
+
+    #[tokio::test]
+    async fn it_builds_an_authorization_uri() {
+        let login = Login::bind(Credentials::new("id", ""), 0).await.unwrap();
+        let uri = login.authorization_uri();
+        let params: Vec<_> = uri.query_pairs().collect();
+
+        assert_eq!(uri.path(), "/api/v1/authorize");
+        assert!(params.contains(&("client_id".into(), "id".into())));
+        assert!(params.contains(&("response_type".into(), "code".into())));
+        assert!(params.contains(&("duration".into(), "permanent".into())));
+        assert!(params.contains(&("redirect_uri".into(), login.redirect_uri().into())));
+        assert!(params.contains(&("scope".into(), SCOPES.into())));
+    }
+
+    #[tokio::test]
+    async fn it_authorizes_with_a_custom_auth_uri() {
+        let login = Login::bind(Credentials::new("id", ""), 0)
+            .await
+            .unwrap()
+            .auth_uri("http://127.0.0.1:8080/");
+        let uri = login.authorization_uri();
+        assert_eq!(uri.host_str(), Some("127.0.0.1"));
+        assert_eq!(uri.port(), Some(8080));
+        assert_eq!(uri.path(), "/api/v1/authorize");
+    }
+
+    #[tokio::test]
+    async fn it_skips_connections_that_send_no_request() {
+        let login = Login::bind(Credentials::new("id", ""), 0).await.unwrap();
+        let addr = login.listener.local_addr().unwrap();
+        let _idle = TcpStream::connect(addr).await.unwrap();
+        let line = request_line(&format!("state={}&code=abc123", login.state));
+        let browser = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(line.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        });
+
+        let code = login
+            .wait_for_code(Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert_eq!(code, "abc123");
+        assert!(browser.await.unwrap().starts_with("HTTP/1.1 200 OK"));
+    }
+
+    #[tokio::test]
+    async fn it_listens_on_localhost() {
+        let login = Login::bind(Credentials::new("id", ""), 0).await.unwrap();
+        assert!(login.redirect_uri().starts_with("http://localhost:"));
+        assert!(login.redirect_uri().ends_with(CALLBACK_PATH));
+    }
+}
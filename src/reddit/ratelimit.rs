@@ -0,0 +1,380 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Scheduling requests within Reddit's rate limits.
+//!
+//! Reddit reports a client's request budget in the `X-Ratelimit-Remaining`,
+//! `X-Ratelimit-Used`, and `X-Ratelimit-Reset` headers of each response.
+//! A [`RateLimiter`] tracks these values and delays requests that would
+//! exceed the budget until the current window resets. Requests that are
+//! rejected anyway, or that fail because of a server error, are retried
+//! after an exponential [backoff](backoff) with random jitter.
+
+use log::debug;
+use reqwest::StatusCode;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::time::{self, Instant};
+
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const USED_HEADER: &str = "x-ratelimit-used";
+const RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// The number of times a failed request is retried.
+pub const MAX_RETRIES: u32 = 3;
+
+/// The delay before the first retry, which is doubled for each later retry.
+const BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// The longest a request will be delayed before it is retried.
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// The number of requests assumed to be available after the budget resets,
+/// until Reddit reports the new budget.
+const RESET_BUDGET: f64 = 10.0;
+
+/// The length of Reddit's rate limit window.
+const RESET_WINDOW: Duration = Duration::from_secs(600);
+
+/// A snapshot of the client's request budget, as reported by Reddit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimit {
+    remaining: f64,
+    used: u64,
+    reset_at: Instant,
+}
+
+impl RateLimit {
+    /// Reads the rate limit from a response's `headers`, which were received
+    /// at `now`.
+    ///
+    /// Returns `None` if the headers are missing or malformed, which is the
+    /// case for unauthenticated requests.
+    pub fn from_headers(headers: &HeaderMap, now: Instant) -> Option<Self> {
+        let header = |name| headers.get(name)?.to_str().ok()?.trim().parse::<f64>().ok();
+        let remaining = header(REMAINING_HEADER)?;
+        let used = header(USED_HEADER)?;
+        let reset = header(RESET_HEADER)?;
+        Some(Self {
+            remaining,
+            used: used as u64,
+            reset_at: now + Duration::from_secs_f64(reset.max(0.0)),
+        })
+    }
+
+    /// A conservative budget for a window that reset at `now`, used until
+    /// Reddit reports the actual budget.
+    fn after_reset(now: Instant) -> Self {
+        Self {
+            remaining: RESET_BUDGET,
+            used: 0,
+            reset_at: now + RESET_WINDOW,
+        }
+    }
+
+    /// The number of requests that can still be made in the current window.
+    pub fn remaining(&self) -> f64 {
+        self.remaining
+    }
+
+    /// The number of requests made in the current window.
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    /// How long the next request must wait, as of `now`, to stay within the
+    /// budget, or `None` if it can be sent immediately.
+    pub fn delay(&self, now: Instant) -> Option<Duration> {
+        (self.remaining < 1.0 && self.reset_at > now).then(|| self.reset_at - now)
+    }
+}
+
+/// Tracks a client's request budget and delays requests to stay within it.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    limit: Mutex<Option<RateLimit>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter that does not know the budget yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits until a request can be sent without exceeding the budget, then
+    /// reserves a request from the budget.
+    ///
+    /// Concurrent callers wait in turn, so a nearly exhausted budget is not
+    /// overrun by several requests at once. Once the budget resets, only a
+    /// few requests are let through until a response reports the new budget.
+    pub async fn acquire(&self) {
+        let mut limit = self.limit.lock().await;
+        if let Some(current) = limit.as_mut() {
+            if let Some(delay) = current.delay(Instant::now()) {
+                debug!(
+                    "Rate limit exhausted, waiting {:.1} secs for reset",
+                    delay.as_secs_f64()
+                );
+                time::sleep(delay).await;
+                *current = RateLimit::after_reset(Instant::now());
+            }
+            current.remaining -= 1.0;
+        }
+    }
+
+    /// Updates the budget from a response's `headers`.
+    pub async fn update(&self, headers: &HeaderMap) {
+        if let Some(new_limit) = RateLimit::from_headers(headers, Instant::now()) {
+            debug!(
+                "Rate limit: {} requests remaining, {} used, resets in {:.0} secs",
+                new_limit.remaining(),
+                new_limit.used(),
+                new_limit
+                    .reset_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs_f64()
+            );
+            *self.limit.lock().await = Some(new_limit);
+        }
+    }
+}
+
+/// True if a request that failed with `status` is worth retrying.
+pub fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// How long to wait before retrying a request for the given `attempt`,
+/// counting from 0.
+///
+/// If Reddit sent a `Retry-After` header with the failed response's
+/// `headers`, it is honored; otherwise the delay grows exponentially with
+/// each attempt. Either way, up to a second of random jitter is added so
+/// concurrent requests do not retry in lockstep.
+pub fn backoff(attempt: u32, headers: &HeaderMap) -> Duration {
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs);
+    let delay =
+        retry_after.unwrap_or_else(|| BASE_DELAY.saturating_mul(2u32.saturating_pow(attempt)));
+    let jitter = BASE_DELAY.mul_f64(fastrand::f64());
+    delay.min(MAX_DELAY) + jitter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::sync::Arc;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    mod rate_limit {
+        use super::*;
+
+        #[test]
+        fn it_parses_headers() {
+            let now = Instant::now();
+            let headers = headers(&[
+                (REMAINING_HEADER, "598.0"),
+                (USED_HEADER, "2"),
+                (RESET_HEADER, "412"),
+            ]);
+            let limit = RateLimit::from_headers(&headers, now).unwrap();
+            assert_eq!(limit.remaining(), 598.0);
+            assert_eq!(limit.used(), 2);
+            assert_eq!(limit.reset_at, now + Duration::from_secs(412));
+        }
+
+        #[test]
+        fn it_ignores_missing_headers() {
+            let headers = headers(&[(REMAINING_HEADER, "598.0")]);
+            assert!(RateLimit::from_headers(&headers, Instant::now()).is_none());
+        }
+
+        #[test]
+        fn it_ignores_malformed_headers() {
+            let headers = headers(&[
+                (REMAINING_HEADER, "lots"),
+                (USED_HEADER, "2"),
+                (RESET_HEADER, "412"),
+            ]);
+            assert!(RateLimit::from_headers(&headers, Instant::now()).is_none());
+        }
+
+        #[test]
+        fn it_does_not_delay_requests_within_the_budget() {
+            let now = Instant::now();
+            let headers = headers(&[
+                (REMAINING_HEADER, "1.0"),
+                (USED_HEADER, "599"),
+                (RESET_HEADER, "412"),
+            ]);
+            let limit = RateLimit::from_headers(&headers, now).unwrap();
+            assert!(limit.delay(now).is_none());
+        }
+
+        #[test]
+        fn it_delays_requests_until_the_budget_resets() {
+            let now = Instant::now();
+            let headers = headers(&[
+                (REMAINING_HEADER, "0.0"),
+                (USED_HEADER, "600"),
+                (RESET_HEADER, "412"),
+            ]);
+            let limit = RateLimit::from_headers(&headers, now).unwrap();
+            assert_eq!(limit.delay(now), Some(Duration::from_secs(412)));
+        }
+
+        #[test]
+        fn it_does_not_delay_requests_after_the_budget_resets() {
+            let now = Instant::now();
+            let headers = headers(&[
+                (REMAINING_HEADER, "0.0"),
+                (USED_HEADER, "600"),
+                (RESET_HEADER, "412"),
+            ]);
+            let limit = RateLimit::from_headers(&headers, now).unwrap();
+            assert!(limit.delay(now + Duration::from_secs(413)).is_none());
+        }
+    }
+
+    mod rate_limiter {
+        use super::*;
+
+        #[tokio::test(start_paused = true)]
+        async fn it_waits_for_the_budget_to_reset() {
+            let limiter = RateLimiter::new();
+            limiter
+                .update(&headers(&[
+                    (REMAINING_HEADER, "0.0"),
+                    (USED_HEADER, "600"),
+                    (RESET_HEADER, "30"),
+                ]))
+                .await;
+
+            let start = Instant::now();
+            limiter.acquire().await;
+            assert!(start.elapsed() >= Duration::from_secs(30));
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn it_reserves_requests_from_the_budget() {
+            let limiter = RateLimiter::new();
+            limiter
+                .update(&headers(&[
+                    (REMAINING_HEADER, "1.0"),
+                    (USED_HEADER, "599"),
+                    (RESET_HEADER, "30"),
+                ]))
+                .await;
+
+            let start = Instant::now();
+            limiter.acquire().await;
+            assert!(start.elapsed() < Duration::from_secs(1));
+            limiter.acquire().await;
+            assert!(start.elapsed() >= Duration::from_secs(30));
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn it_limits_concurrent_requests_after_the_budget_resets() {
+            let limiter = Arc::new(RateLimiter::new());
+            limiter
+                .update(&headers(&[
+                    (REMAINING_HEADER, "0.0"),
+                    (USED_HEADER, "600"),
+                    (RESET_HEADER, "30"),
+                ]))
+                .await;
+
+            let start = Instant::now();
+            let tasks: Vec<_> = (0..RESET_BUDGET as usize + 2)
+                .map(|_| {
+                    let limiter = Arc::clone(&limiter);
+                    tokio::spawn(async move {
+                        limiter.acquire().await;
+                        start.elapsed()
+                    })
+                })
+                .collect();
+            let mut elapsed = Vec::new();
+            for task in tasks {
+                elapsed.push(task.await.unwrap());
+            }
+
+            assert!(
+                elapsed
+                    .iter()
+                    .all(|elapsed| *elapsed >= Duration::from_secs(30))
+            );
+            let after_reset = elapsed
+                .iter()
+                .filter(|elapsed| **elapsed < Duration::from_secs(31))
+                .count();
+            assert_eq!(after_reset, RESET_BUDGET as usize);
+        }
+
+        #[tokio::test]
+        async fn it_does_not_wait_without_a_known_budget() {
+            let limiter = RateLimiter::new();
+            let start = Instant::now();
+            limiter.acquire().await;
+            assert!(start.elapsed() < Duration::from_secs(1));
+        }
+    }
+
+    mod backoff {
+        use super::*;
+
+        #[test]
+        fn it_retries_rate_limited_requests() {
+            assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        }
+
+        #[test]
+        fn it_retries_server_errors() {
+            assert!(is_retryable(StatusCode::INTERNAL_SERVER_ERROR));
+            assert!(is_retryable(StatusCode::SERVICE_UNAVAILABLE));
+        }
+
+        #[test]
+        fn it_does_not_retry_client_errors() {
+            assert!(!is_retryable(StatusCode::NOT_FOUND));
+            assert!(!is_retryable(StatusCode::FORBIDDEN));
+        }
+
+        #[test]
+        fn it_backs_off_exponentially() {
+            for (attempt, secs) in [(0, 1), (1, 2), (2, 4), (3, 8)] {
+                let delay = backoff(attempt, &HeaderMap::new());
+                let base = Duration::from_secs(secs);
+                assert!(
+                    delay >= base && delay < base + BASE_DELAY,
+                    "attempt {attempt}: {delay:?}"
+                );
+            }
+        }
+
+        #[test]
+        fn it_caps_the_delay() {
+            let delay = backoff(20, &HeaderMap::new());
+            assert!(delay < MAX_DELAY + BASE_DELAY, "{delay:?}");
+        }
+
+        #[test]
+        fn it_honors_retry_after() {
+            let delay = backoff(0, &headers(&[("retry-after", "10")]));
+            let base = Duration::from_secs(10);
+            assert!(delay >= base && delay < base + BASE_DELAY, "{delay:?}");
+        }
+    }
+}
@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! Resources that can be retrieved for a Reddit user.
+//!
+//! A [`Resource`] identifies one of the Reddit API endpoints that describe
+//! a user, such as their profile or their comments. Listings carry
+//! [`ListingParams`] that control how the listing is sorted and which page
+//! of it is retrieved.
+
+use std::fmt;
+use std::time::Duration;
+
+/// The order in which a listing is sorted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sort {
+    /// Newest items first.
+    New,
+
+    /// Items that are currently popular first.
+    Hot,
+
+    /// Highest-scoring items first.
+    Top,
+
+    /// Most controversial items first.
+    Controversial,
+}
+
+impl fmt::Display for Sort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sort::New => write!(f, "new"),
+            Sort::Hot => write!(f, "hot"),
+            Sort::Top => write!(f, "top"),
+            Sort::Controversial => write!(f, "controversial"),
+        }
+    }
+}
+
+/// The period of time from which a [top](Sort::Top) or
+/// [controversial](Sort::Controversial) listing is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TimeWindow {
+    /// The past hour.
+    Hour,
+
+    /// The past day.
+    Day,
+
+    /// The past week.
+    Week,
+
+    /// The past month.
+    Month,
+
+    /// The past year.
+    Year,
+
+    /// All time.
+    All,
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeWindow::Hour => write!(f, "hour"),
+            TimeWindow::Day => write!(f, "day"),
+            TimeWindow::Week => write!(f, "week"),
+            TimeWindow::Month => write!(f, "month"),
+            TimeWindow::Year => write!(f, "year"),
+            TimeWindow::All => write!(f, "all"),
+        }
+    }
+}
+
+impl TimeWindow {
+    /// How far back the time window reaches, or `None` for all time.
+    pub fn duration(&self) -> Option<Duration> {
+        const DAY: u64 = 24 * 60 * 60;
+        match self {
+            TimeWindow::Hour => Some(Duration::from_secs(60 * 60)),
+            TimeWindow::Day => Some(Duration::from_secs(DAY)),
+            TimeWindow::Week => Some(Duration::from_secs(7 * DAY)),
+            TimeWindow::Month => Some(Duration::from_secs(30 * DAY)),
+            TimeWindow::Year => Some(Duration::from_secs(365 * DAY)),
+            TimeWindow::All => None,
+        }
+    }
+}
+
+/// Parameters for retrieving a page of a listing.
+///
+/// `ListingParams` follows a builder pattern, starting with
+/// [`ListingParams::default()`], which retrieves the first page of a
+/// listing in Reddit's default order.
+///
+/// # Examples
+///
+/// ```
+/// use usaidwat::reddit::resource::{ListingParams, Sort, TimeWindow};
+/// let params = ListingParams::default()
+///     .sort(Sort::Top)
+///     .time(TimeWindow::Year)
+///     .after("t1_lomc1at");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListingParams {
+    sort: Option<Sort>,
+    time: Option<TimeWindow>,
+    limit: u32,
+    after: Option<String>,
+}
+
+impl Default for ListingParams {
+    fn default() -> Self {
+        Self {
+            sort: None,
+            time: None,
+            limit: Self::DEFAULT_LIMIT,
+            after: None,
+        }
+    }
+}
+
+impl ListingParams {
+    /// The number of items requested per page by default, which is also
+    /// the most Reddit will return.
+    pub const DEFAULT_LIMIT: u32 = 100;
+
+    /// Sorts the listing in the given order.
+    pub fn sort(self, sort: Sort) -> Self {
+        Self {
+            sort: Some(sort),
+            ..self
+        }
+    }
+
+    /// Draws a top or controversial listing from the given time window.
+    pub fn time(self, time: TimeWindow) -> Self {
+        Self {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Requests up to `limit` items per page.
+    pub fn limit(self, limit: u32) -> Self {
+        Self { limit, ..self }
+    }
+
+    /// Retrieves the page following the `after` cursor.
+    pub fn after(self, after: impl Into<String>) -> Self {
+        Self {
+            after: Some(after.into()),
+            ..self
+        }
+    }
+
+    /// The order in which the listing is sorted, if not Reddit's default.
+    pub fn sort_order(&self) -> Option<Sort> {
+        self.sort
+    }
+
+    /// The time window of the listing, if not Reddit's default.
+    pub fn time_window(&self) -> Option<TimeWindow> {
+        self.time
+    }
+
+    /// The number of items requested per page.
+    pub fn page_limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// The cursor of the page to retrieve, or `None` for the first page.
+    pub fn cursor(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
+
+    /// The query string for these parameters, including the leading `?`.
+    fn query_string(&self) -> String {
+        let mut params = vec![format!("limit={}", self.limit)];
+        if let Some(sort) = self.sort {
+            params.push(format!("sort={sort}"));
+        }
+        if let Some(time) = self.time {
+            params.push(format!("t={time}"));
+        }
+        if let Some(after) = &self.after {
+            params.push(format!("after={after}"));
+        }
+        format!("?{}", params.join("&"))
+    }
+}
+
+/// A resource that can be retrieved for a Reddit user.
+///
+/// [`Info`](Resource::Info) is the exception: it retrieves arbitrary
+/// things by their fullnames, such as the comments a user replied to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Resource {
+    /// The user's profile.
+    About,
+
+    /// The user's comments.
+    Comments(ListingParams),
+
+    /// The user's posts.
+    Submitted(ListingParams),
+
+    /// Things the user has saved. Only available for the logged-in user.
+    Saved(ListingParams),
+
+    /// The user's comments and posts that have received awards.
+    Gilded(ListingParams),
+
+    /// The user's trophies.
+    Trophies,
+
+    /// The subreddits the user moderates.
+    Moderated,
+
+    /// The things with the given fullnames, regardless of who posted them.
+    Info(Vec<String>),
+}
+
+impl Resource {
+    /// The name of the resource, like "comments".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Resource::About => "about",
+            Resource::Comments(_) => "comments",
+            Resource::Submitted(_) => "submitted",
+            Resource::Saved(_) => "saved",
+            Resource::Gilded(_) => "gilded",
+            Resource::Trophies => "trophies",
+            Resource::Moderated => "moderated_subreddits",
+            Resource::Info(_) => "info",
+        }
+    }
+
+    /// The parameters of a listing, or `None` if the resource is not a
+    /// listing.
+    pub fn params(&self) -> Option<&ListingParams> {
+        match self {
+            Resource::Comments(params)
+            | Resource::Submitted(params)
+            | Resource::Saved(params)
+            | Resource::Gilded(params) => Some(params),
+            Resource::About | Resource::Trophies | Resource::Moderated | Resource::Info(_) => None,
+        }
+    }
+
+    /// The path and query string of the resource for `username`, relative
+    /// to the base URL of the Reddit API.
+    pub fn path(&self, username: &str) -> String {
+        match self {
+            Resource::Trophies => format!("/api/v1/user/{username}/trophies"),
+            Resource::Info(ids) => format!("/api/info.json?id={}", ids.join(",")),
+            _ => {
+                let name = self.name();
+                let qs = self
+                    .params()
+                    .map(ListingParams::query_string)
+                    .unwrap_or_default();
+                format!("/user/{username}/{name}.json{qs}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Resource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_path_for_profiles() {
+        assert_eq!(Resource::About.path("mipadi"), "/user/mipadi/about.json");
+    }
+
+    #[test]
+    fn it_returns_a_path_with_listing_limits() {
+        let resource = Resource::Comments(ListingParams::default());
+        assert_eq!(
+            resource.path("mipadi"),
+            "/user/mipadi/comments.json?limit=100"
+        );
+    }
+
+    #[test]
+    fn it_returns_a_path_with_a_page_cursor() {
+        let resource = Resource::Submitted(ListingParams::default().after("t3_2au1ki"));
+        assert_eq!(
+            resource.path("mipadi"),
+            "/user/mipadi/submitted.json?limit=100&after=t3_2au1ki"
+        );
+    }
+
+    #[test]
+    fn it_returns_a_path_with_sort_parameters() {
+        let params = ListingParams::default()
+            .sort(Sort::Top)
+            .time(TimeWindow::Year)
+            .limit(25);
+        assert_eq!(
+            Resource::Gilded(params).path("mipadi"),
+            "/user/mipadi/gilded.json?limit=25&sort=top&t=year"
+        );
+    }
+
+    #[test]
+    fn it_returns_paths_for_other_resources() {
+        assert_eq!(
+            Resource::Saved(ListingParams::default()).path("mipadi"),
+            "/user/mipadi/saved.json?limit=100"
+        );
+        assert_eq!(
+            Resource::Trophies.path("mipadi"),
+            "/api/v1/user/mipadi/trophies"
+        );
+        assert_eq!(
+            Resource::Moderated.path("mipadi"),
+            "/user/mipadi/moderated_subreddits.json"
+        );
+        assert_eq!(
+            Resource::Info(vec![String::from("t1_mlqtxuw"), String::from("t1_mlq2v9d")])
+                .path("mipadi"),
+            "/api/info.json?id=t1_mlqtxuw,t1_mlq2v9d"
+        );
+    }
+}
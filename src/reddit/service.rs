@@ -7,8 +7,26 @@
 //! with the Reddit API over HTTPS, essentially a specialized HTTPS client
 //! specifically for Reddit.
 
+use crate::config::ConfigFile;
+use crate::reddit::auth::{AccessToken, Credentials, TokenStore};
+use crate::reddit::ratelimit::{self, RateLimiter};
+use crate::reddit::resource::Resource;
+use crate::reddit::user_agent::UserAgent;
 use hypertyper::prelude::*;
-use reqwest::header;
+use log::{debug, warn};
+use reqwest::{Response, StatusCode, Url, header};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use tokio::sync::Mutex;
+use tokio::time;
+
+/// Environment variable overriding the base URL of the Reddit API.
+pub const BASE_URL_VAR: &str = "USAIDWAT_REDDIT_BASE_URL";
+
+/// Environment variable overriding the base URL of Reddit's OAuth2
+/// endpoints.
+pub const AUTH_URL_VAR: &str = "USAIDWAT_REDDIT_AUTH_URL";
 
 /// A service for retrieving information for Reddit users.
 ///
@@ -18,128 +36,575 @@ use reqwest::header;
 pub trait Service: HttpGet {
     /// Performs a GET request to the `resource` associated with the given
     /// `username` and returns it as a parsed JSON response.
+    ///
+    /// For listings such as comments and submissions, the resource's
+    /// [parameters](crate::reddit::resource::ListingParams) determine which
+    /// page is retrieved and how it is sorted.
     fn get_resource(
         &self,
         username: &str,
-        resource: &str,
+        resource: &Resource,
     ) -> impl Future<Output = HttpResult<String>> + Send;
+
+    /// True if the service's data comes from Reddit, rather than from a
+    /// local copy of it such as an [archive](crate::archive::Archive).
+    ///
+    /// Only Reddit knows whether a user's history is hidden, so missing
+    /// activity is only attributed to a hidden history by live services.
+    fn is_live(&self) -> bool {
+        true
+    }
 }
 
 /// A service that contacts the Reddit API directly to retrieve information.
+///
+/// If the service has [credentials](Credentials), it authenticates with
+/// Reddit's OAuth2 API and sends its requests to `oauth.reddit.com`;
+/// otherwise it falls back to Reddit's public (and, nowadays, mostly
+/// forbidden) API at `www.reddit.com`.
+///
+/// Both base URLs can be overridden, which is useful for pointing the
+/// service at a local stand-in for Reddit.
+///
+/// Requests are scheduled to stay within Reddit's
+/// [rate limits](crate::reddit::ratelimit), and requests that are rate
+/// limited or fail because of a server error are retried.
+#[derive(Debug)]
 pub struct RedditService {
     client: HttpClient,
+    credentials: Option<Credentials>,
+    token: Mutex<Option<AccessToken>>,
+    token_store: Option<TokenStore>,
+    rate_limiter: RateLimiter,
+    base_uri: Option<String>,
+    auth_uri: Option<String>,
 }
 
 impl Default for RedditService {
-    /// Creates a new Reddit service.
+    /// Creates a new Reddit service using credentials from the environment
+    /// or the user's configuration file, if any.
+    ///
+    /// Access tokens are cached in the default [`TokenStore`], and requests
+    /// are sent with the [network settings](crate::config::NetworkConfig)
+    /// in the user's configuration file.
+    ///
+    /// The base URLs of the Reddit API and its OAuth2 endpoints can be
+    /// overridden with the `$USAIDWAT_REDDIT_BASE_URL` and
+    /// `$USAIDWAT_REDDIT_AUTH_URL` environment variables, respectively.
     fn default() -> Self {
-        let factory = HttpClientFactory::with_user_agent(Self::user_agent());
-        let client = factory.create();
-        Self { client }
+        let mut service = Self::new(Credentials::load()).client(Self::http_client_or_default());
+        if let Some(store) = TokenStore::default_location() {
+            service = service.token_store(store);
+        }
+        if let Ok(base_uri) = env::var(BASE_URL_VAR) {
+            service = service.base_uri(base_uri);
+        }
+        if let Ok(auth_uri) = env::var(AUTH_URL_VAR) {
+            service = service.auth_uri(auth_uri);
+        }
+        service
     }
 }
 
 impl RedditService {
-    pub fn user_agent() -> String {
-        format!(
-            "{}/{} by u/mipadi",
-            env!("CARGO_PKG_NAME"),
-            env!("CARGO_PKG_VERSION")
-        )
+    const PUBLIC_BASE_URI: &'static str = "https://www.reddit.com";
+    const OAUTH_BASE_URI: &'static str = "https://oauth.reddit.com";
+    const AUTH_BASE_URI: &'static str = "https://www.reddit.com";
+
+    /// Creates a new Reddit service that authenticates with the given
+    /// `credentials`, or sends unauthenticated requests if `credentials`
+    /// is `None`.
+    pub fn new(credentials: Option<Credentials>) -> Self {
+        let factory = HttpClientFactory::with_user_agent(UserAgent::default().to_string());
+        let client = factory.create();
+        Self {
+            client,
+            credentials,
+            token: Mutex::new(None),
+            token_store: None,
+            rate_limiter: RateLimiter::new(),
+            base_uri: None,
+            auth_uri: None,
+        }
+    }
+
+    /// Sends requests with the given HTTP `client`, which can be configured
+    /// with a proxy server, additional root certificates, or timeouts.
+    ///
+    /// The client should identify itself with a [`UserAgent`], as Reddit
+    /// requires.
+    pub fn client(self, client: HttpClient) -> Self {
+        Self { client, ..self }
+    }
+
+    /// Sends API requests to `base_uri` instead of Reddit.
+    ///
+    /// Requests are sent to `{base_uri}/user/{username}/{resource}.json`,
+    /// whether or not the service is authenticated.
+    pub fn base_uri(self, base_uri: impl Into<String>) -> Self {
+        Self {
+            base_uri: Some(base_uri.into().trim_end_matches('/').to_string()),
+            ..self
+        }
+    }
+
+    /// Requests access tokens from `{auth_uri}/api/v1/access_token` instead
+    /// of Reddit.
+    pub fn auth_uri(self, auth_uri: impl Into<String>) -> Self {
+        Self {
+            auth_uri: Some(auth_uri.into().trim_end_matches('/').to_string()),
+            ..self
+        }
+    }
+
+    /// Caches access tokens in the given `store`, so they can be reused
+    /// by later instances of the service until they expire.
+    pub fn token_store(self, token_store: TokenStore) -> Self {
+        Self {
+            token_store: Some(token_store),
+            ..self
+        }
+    }
+
+    /// True if the service has credentials with which to authenticate.
+    pub fn is_authenticated(&self) -> bool {
+        self.credentials.is_some()
     }
 
-    fn query_string(&self, resource: &str) -> &str {
-        match resource {
-            "comments" => "?limit=100",
-            "submitted" => "?limit=100",
-            _ => "",
+    /// Creates an HTTP client for contacting Reddit with the User-Agent
+    /// and network settings in the user's configuration file.
+    ///
+    /// Returns an error if the configuration file cannot be loaded, or if
+    /// its User-Agent or network settings are invalid.
+    pub(crate) fn http_client() -> anyhow::Result<HttpClient> {
+        let config = ConfigFile::load()?;
+        let user_agent = UserAgent::from_config(config.reddit())?;
+        config.network().client(&user_agent.to_string())
+    }
+
+    /// Creates an HTTP client like [`RedditService::http_client()`], but
+    /// logs a warning and falls back to the default settings if the
+    /// configured settings cannot be applied.
+    ///
+    /// The command-line program checks the settings before it sends any
+    /// requests, so it never falls back.
+    pub(crate) fn http_client_or_default() -> HttpClient {
+        Self::http_client().unwrap_or_else(|err| {
+            warn!("Could not apply configuration file: {err:#}");
+            HttpClientFactory::with_user_agent(UserAgent::default().to_string()).create()
+        })
+    }
+
+    /// The base URI of Reddit's OAuth2 endpoints, or the URI in
+    /// `$USAIDWAT_REDDIT_AUTH_URL` if it is set.
+    pub(crate) fn default_auth_uri() -> String {
+        env::var(AUTH_URL_VAR)
+            .map(|auth_uri| auth_uri.trim_end_matches('/').to_string())
+            .unwrap_or_else(|_| String::from(Self::AUTH_BASE_URI))
+    }
+
+    fn api_base_uri(&self) -> &str {
+        match &self.base_uri {
+            Some(base_uri) => base_uri,
+            None if self.is_authenticated() => Self::OAUTH_BASE_URI,
+            None => Self::PUBLIC_BASE_URI,
         }
     }
 
-    fn uri(&self, username: &str, resource: &str) -> String {
-        let qs = self.query_string(resource);
-        format!("https://www.reddit.com/user/{username}/{resource}.json{qs}")
+    fn token_uri(&self) -> String {
+        let auth_uri = self.auth_uri.as_deref().unwrap_or(Self::AUTH_BASE_URI);
+        format!("{auth_uri}/api/v1/access_token")
+    }
+
+    pub(crate) fn uri(&self, username: &str, resource: &Resource) -> String {
+        let base = self.api_base_uri();
+        let path = resource.path(username);
+        format!("{base}{path}")
+    }
+
+    /// Returns a valid access token, requesting a new one if the service
+    /// does not have one yet or its current token has expired.
+    ///
+    /// Tokens are read from and written to the service's token store,
+    /// if it has one.
+    ///
+    /// Returns `None` if the service has no credentials.
+    async fn access_token(&self) -> HttpResult<Option<AccessToken>> {
+        let Some(credentials) = &self.credentials else {
+            return Ok(None);
+        };
+
+        let mut token = self.token.lock().await;
+        if token.is_none() {
+            *token = self
+                .token_store
+                .as_ref()
+                .and_then(|store| store.load(credentials.client_id()));
+        }
+
+        match token.as_ref() {
+            Some(current) if !current.is_expired() => Ok(Some(current.clone())),
+            _ => {
+                let refresh_token = self
+                    .token_store
+                    .as_ref()
+                    .and_then(|store| store.refresh_token(credentials.client_id()));
+                let new_token = match refresh_token {
+                    Some(refresh_token) => {
+                        credentials
+                            .refresh_token(&self.client, self.token_uri(), &refresh_token)
+                            .await?
+                    }
+                    None => {
+                        credentials
+                            .request_token(&self.client, self.token_uri())
+                            .await?
+                    }
+                };
+                debug!(
+                    "Obtained access token expiring at {}",
+                    new_token.expires_at()
+                );
+                if let Some(store) = &self.token_store
+                    && let Err(err) = store.save(credentials.client_id(), &new_token)
+                {
+                    warn!(
+                        "Could not save access token to {}: {err}",
+                        store.path().display()
+                    );
+                }
+                *token = Some(new_token.clone());
+                Ok(Some(new_token))
+            }
+        }
+    }
+
+    /// Discards the current access token, forcing a new one to be requested
+    /// the next time the service needs one.
+    ///
+    /// A refresh token saved by logging in is kept.
+    async fn invalidate_token(&self) {
+        *self.token.lock().await = None;
+        if let Some(store) = &self.token_store
+            && let Some(credentials) = &self.credentials
+            && let Err(err) = store.discard_access_token(credentials.client_id())
+        {
+            warn!("Could not clear {}: {err}", store.path().display());
+        }
+    }
+
+    /// Sends a GET request to `uri`, authenticated if the service has
+    /// credentials, once the rate limit allows it.
+    async fn send(&self, uri: Url) -> HttpResult<Response> {
+        let mut request = self.client.get(uri);
+        if let Some(token) = self.access_token().await? {
+            request = request.bearer_auth(token.secret());
+        }
+        self.rate_limiter.acquire().await;
+        let resp = request.send().await?;
+        self.rate_limiter.update(resp.headers()).await;
+        Ok(resp)
+    }
+
+    /// Sends a GET request to `uri`, retrying if Reddit rejects the
+    /// service's access token, rate limits the request, or fails.
+    ///
+    /// A new access token is only requested once, no matter how many times
+    /// the request is retried.
+    async fn send_with_retries(&self, uri: Url) -> HttpResult<Response> {
+        let mut attempt = 0;
+        let mut refreshed = false;
+        loop {
+            let mut resp = self.send(uri.clone()).await?;
+
+            if resp.status() == StatusCode::UNAUTHORIZED && self.is_authenticated() && !refreshed {
+                debug!("Access token was rejected, retrying with a new token");
+                self.invalidate_token().await;
+                refreshed = true;
+                resp = self.send(uri.clone()).await?;
+            }
+
+            if !ratelimit::is_retryable(resp.status()) || attempt >= ratelimit::MAX_RETRIES {
+                return Ok(resp);
+            }
+
+            let delay = ratelimit::backoff(attempt, resp.headers());
+            debug!(
+                "Request failed with {}, retrying in {:.1} secs",
+                resp.status(),
+                delay.as_secs_f64()
+            );
+            time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+impl RedditService {
+    /// Sends a GET request to a Reddit API endpoint and returns the response,
+    /// whether or not it was successful.
+    ///
+    /// If Reddit rejects the service's access token, the request is retried
+    /// once with a new token. Requests that are rate limited or fail because
+    /// of a server error are retried a few times with an increasing delay.
+    pub async fn fetch<U>(&self, uri: U) -> HttpResult<RawResponse>
+    where
+        U: IntoUrl + Send,
+    {
+        let resp = self.send_with_retries(uri.into_url()?).await?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = resp.text().await?;
+        Ok(RawResponse {
+            status,
+            headers,
+            body,
+        })
     }
 }
 
 impl HttpGet for RedditService {
     /// Sends a GET request to a Reddit API endpoint and returns the raw body.
+    ///
+    /// See [`RedditService::fetch()`] for details on how failed requests
+    /// are retried.
     async fn get<U>(&self, uri: U) -> HttpResult<String>
     where
         U: IntoUrl + Send,
     {
-        let resp = self.client.get(uri).send().await?;
+        self.fetch(uri).await?.into_body()
+    }
+}
+
+impl Service for RedditService {
+    async fn get_resource(&self, username: &str, resource: &Resource) -> HttpResult<String> {
+        let uri = self.uri(username, resource);
+        self.get(&uri).await
+    }
+}
 
-        if !resp.status().is_success() {
-            Err(HttpError::Http(resp.status()))
+/// A response from the Reddit API, as received over the wire.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RawResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+}
+
+impl RawResponse {
+    /// The response's HTTP status code.
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// The value of the response header with the given `name`, if any.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Removes all headers except those in `names`, ignoring case.
+    pub fn retain_headers(&mut self, names: &[&str]) {
+        self.headers
+            .retain(|key, _| names.iter().any(|name| key.eq_ignore_ascii_case(name)));
+    }
+
+    /// Returns the response's body if the response was successful and
+    /// contains JSON data; otherwise returns an error.
+    pub fn into_body(self) -> HttpResult<String> {
+        if !self.status().is_success() {
+            Err(HttpError::Http(self.status()))
         } else {
-            let content_type = resp
-                .headers()
-                .get(header::CONTENT_TYPE)
-                .ok_or(HttpError::MissingContentType)?
-                .to_str()?;
+            let content_type = self
+                .header(header::CONTENT_TYPE.as_str())
+                .ok_or(HttpError::MissingContentType)?;
             if !content_type.starts_with("application/json") {
                 Err(HttpError::UnexpectedContentType(content_type.to_string()))
             } else {
-                Ok(resp.text().await?)
+                Ok(self.body)
             }
         }
     }
 }
 
-impl Service for RedditService {
-    async fn get_resource(&self, username: &str, resource: &str) -> HttpResult<String> {
-        let uri = self.uri(username, resource);
-        self.get(&uri).await
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::reddit::resource::ListingParams;
+
+    fn authenticated_service() -> RedditService {
+        RedditService::new(Some(Credentials::new("id", "secret")))
+    }
 
     #[test]
-    fn it_returns_a_query_string_with_comment_limits() {
-        let service = RedditService::default();
-        let qs = service.query_string("comments");
-        assert_eq!(qs, "?limit=100");
+    fn it_returns_a_uri_for_comments() {
+        let service = RedditService::new(None);
+        let actual_uri = service.uri("mipadi", &Resource::Comments(ListingParams::default()));
+        let expected_uri = "https://www.reddit.com/user/mipadi/comments.json?limit=100";
+        assert_eq!(actual_uri, expected_uri);
     }
 
     #[test]
-    fn it_returns_a_query_string_with_post_limits() {
-        let service = RedditService::default();
-        let qs = service.query_string("submitted");
-        assert_eq!(qs, "?limit=100");
+    fn it_returns_a_uri_for_posts() {
+        let service = RedditService::new(None);
+        let actual_uri = service.uri("mipadi", &Resource::Submitted(ListingParams::default()));
+        let expected_uri = "https://www.reddit.com/user/mipadi/submitted.json?limit=100";
+        assert_eq!(actual_uri, expected_uri);
     }
 
     #[test]
-    fn it_returns_an_empty_query_string_for_profiles() {
-        let service = RedditService::default();
-        let qs = service.query_string("about");
-        assert_eq!(qs, "");
+    fn it_returns_a_uri_for_the_next_page_of_posts() {
+        let service = RedditService::new(None);
+        let actual_uri = service.uri(
+            "mipadi",
+            &Resource::Submitted(ListingParams::default().after("t3_2au1ki")),
+        );
+        let expected_uri =
+            "https://www.reddit.com/user/mipadi/submitted.json?limit=100&after=t3_2au1ki";
+        assert_eq!(actual_uri, expected_uri);
     }
 
     #[test]
-    fn it_returns_a_uri_for_comments() {
-        let service = RedditService::default();
-        let actual_uri = service.uri("mipadi", "comments");
-        let expected_uri = "https://www.reddit.com/user/mipadi/comments.json?limit=100";
+    fn it_returns_a_uri_for_profiles() {
+        let service = RedditService::new(None);
+        let actual_uri = service.uri("mipadi", &Resource::About);
+        let expected_uri = "https://www.reddit.com/user/mipadi/about.json";
         assert_eq!(actual_uri, expected_uri);
     }
 
     #[test]
-    fn it_returns_a_uri_for_posts() {
-        let service = RedditService::default();
-        let actual_uri = service.uri("mipadi", "submitted");
-        let expected_uri = "https://www.reddit.com/user/mipadi/submitted.json?limit=100";
+    fn it_is_not_authenticated_without_credentials() {
+        let service = RedditService::new(None);
+        assert!(!service.is_authenticated());
+    }
+
+    #[test]
+    fn it_is_authenticated_with_credentials() {
+        let service = authenticated_service();
+        assert!(service.is_authenticated());
+    }
+
+    #[test]
+    fn it_returns_an_oauth_uri_for_comments_when_authenticated() {
+        let service = authenticated_service();
+        let actual_uri = service.uri("mipadi", &Resource::Comments(ListingParams::default()));
+        let expected_uri = "https://oauth.reddit.com/user/mipadi/comments.json?limit=100";
         assert_eq!(actual_uri, expected_uri);
     }
 
     #[test]
-    fn it_returns_a_uri_for_profiles() {
-        let service = RedditService::default();
-        let actual_uri = service.uri("mipadi", "about");
-        let expected_uri = "https://www.reddit.com/user/mipadi/about.json";
+    fn it_returns_an_oauth_uri_for_profiles_when_authenticated() {
+        let service = authenticated_service();
+        let actual_uri = service.uri("mipadi", &Resource::About);
+        let expected_uri = "https://oauth.reddit.com/user/mipadi/about.json";
+        assert_eq!(actual_uri, expected_uri);
+    }
+
+    #[test]
+    fn it_returns_a_uri_with_a_custom_base_uri() {
+        let service = RedditService::new(None).base_uri("http://localhost:8080/");
+        let actual_uri = service.uri("mipadi", &Resource::About);
+        let expected_uri = "http://localhost:8080/user/mipadi/about.json";
+        assert_eq!(actual_uri, expected_uri);
+    }
+
+    #[test]
+    fn it_returns_a_uri_with_a_custom_base_uri_when_authenticated() {
+        let service = authenticated_service().base_uri("http://localhost:8080");
+        let actual_uri = service.uri("mipadi", &Resource::About);
+        let expected_uri = "http://localhost:8080/user/mipadi/about.json";
         assert_eq!(actual_uri, expected_uri);
     }
+
+    #[test]
+    fn it_returns_the_reddit_token_uri() {
+        let service = authenticated_service();
+        let expected_uri = "https://www.reddit.com/api/v1/access_token";
+        assert_eq!(service.token_uri(), expected_uri);
+    }
+
+    #[test]
+    fn it_returns_a_custom_token_uri() {
+        let service = authenticated_service().auth_uri("http://localhost:8080/");
+        let expected_uri = "http://localhost:8080/api/v1/access_token";
+        assert_eq!(service.token_uri(), expected_uri);
+    }
+
+    #[tokio::test]
+    async fn it_has_no_access_token_without_credentials() {
+        let service = RedditService::new(None);
+        let token = service.access_token().await.unwrap();
+        assert!(token.is_none());
+    }
+
+    mod raw_response {
+        use super::*;
+
+        fn response(status: u16, content_type: Option<&str>) -> RawResponse {
+            let headers = content_type
+                .map(|content_type| (String::from("content-type"), String::from(content_type)))
+                .into_iter()
+                .collect();
+            RawResponse {
+                status,
+                headers,
+                body: String::from("{}"),
+            }
+        }
+
+        #[test]
+        fn it_returns_the_body_of_json_responses() {
+            let resp = response(200, Some("application/json; charset=UTF-8"));
+            assert_eq!(resp.into_body().unwrap(), "{}");
+        }
+
+        #[test]
+        fn it_returns_an_error_for_unsuccessful_responses() {
+            let resp = response(404, Some("application/json"));
+            assert!(matches!(
+                resp.into_body(),
+                Err(HttpError::Http(StatusCode::NOT_FOUND))
+            ));
+        }
+
+        #[test]
+        fn it_returns_an_error_for_missing_content_types() {
+            let resp = response(200, None);
+            assert!(matches!(
+                resp.into_body(),
+                Err(HttpError::MissingContentType)
+            ));
+        }
+
+        #[test]
+        fn it_returns_an_error_for_unexpected_content_types() {
+            let resp = response(200, Some("text/html"));
+            assert!(matches!(
+                resp.into_body(),
+                Err(HttpError::UnexpectedContentType(_))
+            ));
+        }
+
+        #[test]
+        fn it_finds_headers_case_insensitively() {
+            let resp = response(200, Some("application/json"));
+            assert_eq!(resp.header("Content-Type"), Some("application/json"));
+        }
+
+        #[test]
+        fn it_retains_only_the_given_headers() {
+            let mut resp = response(200, Some("application/json"));
+            resp.headers
+                .insert(String::from("set-cookie"), String::from("session=secret"));
+            resp.retain_headers(&["Content-Type"]);
+            assert_eq!(resp.header("content-type"), Some("application/json"));
+            assert_eq!(resp.header("set-cookie"), None);
+        }
+    }
 }
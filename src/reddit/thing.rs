@@ -7,12 +7,39 @@
 //! any element of the Reddit system: users, posts, comments, etc. This
 //! module encapsulates that idea and provides an easy way to more or less
 //! work with JSON data from the Reddit API.
+//!
+//! # Serialization
+//!
+//! [`User`], [`About`], [`Comment`], and [`Submission`] can be serialized
+//! with serde. Things are serialized with the same fields, under the same
+//! names, that the Reddit API returns for them, except that:
+//!
+//! - Timestamps, like `created_utc` and `edited`, are ISO-8601 strings, such
+//!   as `"2008-03-31T22:55:26Z"`, instead of Unix timestamps, and `edited`
+//!   is `null` for things that have never been edited. Things that Reddit
+//!   only reports as edited, without saying when, keep `edited` as `true`.
+//! - Comments and submissions include their full `permalink` URL.
+//! - Fields that Reddit reports as `null` when they do not apply have their
+//!   default values instead.
+//!
+//! A `User` is serialized as an object with its `about` data and lists of
+//! its `comments` and `submissions`. Things can be deserialized from either
+//! form, so serialized things can also be wrapped in Reddit's response
+//! format and passed to [`User::parse`].
 
 use crate::filter::Searchable;
 use crate::text;
 use discount::terminal;
 use horologe::{DateTime, Utc, age::HasAge};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// The base URL of permalinks to things on Reddit.
+const REDDIT_URL: &str = "https://www.reddit.com";
+
+/// The link karma every Reddit account starts with, before it has posted
+/// anything.
+const STARTING_LINK_KARMA: i64 = 1;
 
 /// A [thing](self) that is attached to a subreddit.
 pub trait HasSubreddit {
@@ -91,7 +118,7 @@ pub trait Removable {
 }
 
 /// A Reddit user account.
-#[derive(Debug)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct User {
     about: About,
     comments: Vec<Comment>,
@@ -99,15 +126,46 @@ pub struct User {
 }
 
 /// Reddit user account data.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct About {
     name: String,
     id: String,
-    #[serde(deserialize_with = "from_timestamp_f64")]
+    #[serde(deserialize_with = "from_timestamp")]
     created_utc: DateTime<Utc>,
     link_karma: i64,
     comment_karma: i64,
+    #[serde(default)]
+    total_karma: Option<i64>,
+    #[serde(default, deserialize_with = "from_nullable")]
+    awardee_karma: i64,
+    #[serde(default, deserialize_with = "from_nullable")]
+    awarder_karma: i64,
+    #[serde(default, deserialize_with = "from_nullable")]
+    verified: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    has_verified_email: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    is_mod: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    is_gold: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    is_employee: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    icon_img: String,
+    #[serde(default)]
+    subreddit: Option<Profile>,
+}
+
+/// The subreddit that holds a user's profile.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Profile {
+    #[serde(default, deserialize_with = "from_nullable")]
+    title: String,
+    #[serde(default, deserialize_with = "from_nullable")]
+    public_description: String,
+    #[serde(default, deserialize_with = "from_nullable")]
+    over_18: bool,
 }
 
 /// A Reddit comment.
@@ -116,24 +174,131 @@ pub struct About {
 pub struct Comment {
     id: String,
     name: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    parent_id: String,
     subreddit_id: String,
     subreddit: String,
+    // Comments retrieved by ID rather than from a user's listing do not
+    // include the title of the post they belong to.
+    #[serde(default)]
     link_title: String,
     link_id: String,
-    #[serde(deserialize_with = "from_timestamp_f64")]
+    #[serde(deserialize_with = "from_timestamp")]
     created_utc: DateTime<Utc>,
     body: String,
+    #[serde(default, deserialize_with = "from_edited")]
+    edited: Edited,
     ups: i64,
     downs: i64,
     score: i64,
+    #[serde(default, deserialize_with = "from_nullable")]
+    controversiality: u8,
+    #[serde(default, deserialize_with = "from_nullable")]
+    gilded: u64,
+    #[serde(default, deserialize_with = "from_distinguished")]
+    distinguished: Option<Distinguished>,
+    #[serde(default, deserialize_with = "from_nullable")]
+    stickied: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    is_submitter: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    over_18: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    link_author: String,
+    #[serde(default)]
+    num_comments: Option<u64>,
+}
+
+/// The role in which a user posted a comment, if they chose to mark it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Distinguished {
+    /// Posted as a moderator of the subreddit.
+    Moderator,
+
+    /// Posted as a Reddit administrator.
+    Admin,
+
+    /// Posted with a special distinction granted by Reddit.
+    Special,
+}
+
+impl fmt::Display for Distinguished {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Distinguished::Moderator => write!(f, "moderator"),
+            Distinguished::Admin => write!(f, "admin"),
+            Distinguished::Special => write!(f, "special"),
+        }
+    }
+}
+
+impl Serialize for Distinguished {
+    /// Serializes the role as Reddit reports it, like `"moderator"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Something notable about a comment, which can be used to select comments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CommentFlag {
+    /// The comment has been edited.
+    Edited,
+
+    /// The comment was posted by the author of the post it belongs to.
+    Op,
+
+    /// The comment was distinguished by a moderator.
+    Mod,
+
+    /// The comment was posted in a post marked NSFW.
+    Nsfw,
+
+    /// The comment has many upvotes and many downvotes.
+    Controversial,
+
+    /// The comment has received an award.
+    Gilded,
+
+    /// The comment is pinned to the top of its post.
+    Stickied,
+}
+
+impl CommentFlag {
+    const ALL: [CommentFlag; 7] = [
+        CommentFlag::Edited,
+        CommentFlag::Op,
+        CommentFlag::Mod,
+        CommentFlag::Nsfw,
+        CommentFlag::Controversial,
+        CommentFlag::Gilded,
+        CommentFlag::Stickied,
+    ];
+}
+
+impl fmt::Display for CommentFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommentFlag::Edited => write!(f, "edited"),
+            CommentFlag::Op => write!(f, "op"),
+            CommentFlag::Mod => write!(f, "mod"),
+            CommentFlag::Nsfw => write!(f, "nsfw"),
+            CommentFlag::Controversial => write!(f, "controversial"),
+            CommentFlag::Gilded => write!(f, "gilded"),
+            CommentFlag::Stickied => write!(f, "stickied"),
+        }
+    }
 }
 
 /// A Reddit Post.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[allow(dead_code)]
 pub struct Submission {
     id: String,
     name: String,
+    #[serde(deserialize_with = "from_permalink", serialize_with = "to_permalink")]
     permalink: String,
     author: String,
     domain: String,
@@ -142,15 +307,155 @@ pub struct Submission {
     url: String,
     title: String,
     selftext: String,
-    #[serde(deserialize_with = "from_timestamp_f64")]
+    #[serde(deserialize_with = "from_timestamp")]
     created_utc: DateTime<Utc>,
+    #[serde(default, deserialize_with = "from_edited")]
+    edited: Edited,
     num_comments: u64,
     ups: i64,
     downs: i64,
     score: i64,
+    #[serde(default)]
+    link_flair_text: Option<String>,
+    #[serde(default)]
+    upvote_ratio: Option<f64>,
+    #[serde(default, deserialize_with = "from_nullable")]
+    over_18: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    spoiler: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    locked: bool,
+    #[serde(default, deserialize_with = "from_nullable")]
+    is_video: bool,
+    #[serde(default)]
+    post_hint: Option<String>,
+    #[serde(default, deserialize_with = "from_nullable")]
+    num_crossposts: u64,
+    #[serde(default, deserialize_with = "from_removed_by_category")]
+    removed_by_category: Option<RemovalCategory>,
+}
+
+/// The kind of content a post links to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MediaType {
+    /// A self post, which only has text.
+    Text,
+
+    /// A link to a web page.
+    Link,
+
+    /// An image.
+    Image,
+
+    /// A video hosted by Reddit.
+    Video,
+
+    /// Media embedded from another site, like a YouTube video.
+    Embed,
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaType::Text => write!(f, "text"),
+            MediaType::Link => write!(f, "link"),
+            MediaType::Image => write!(f, "image"),
+            MediaType::Video => write!(f, "video"),
+            MediaType::Embed => write!(f, "embed"),
+        }
+    }
+}
+
+/// Who removed a post, or why.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemovalCategory {
+    /// Removed by a moderator of the subreddit.
+    Moderator,
+
+    /// Held for review by AutoModerator.
+    AutomodFiltered,
+
+    /// Removed by the post's author.
+    Author,
+
+    /// Deleted by the post's author.
+    Deleted,
+
+    /// Removed by Reddit.
+    Reddit,
+
+    /// Removed by Reddit's Anti-Evil Operations team.
+    AntiEvilOps,
+
+    /// Removed by Reddit's Community team.
+    CommunityOps,
+
+    /// Removed in response to a legal request.
+    ContentTakedown,
+
+    /// Removed in response to a copyright notice.
+    CopyrightTakedown,
+
+    /// Removed for a reason this version of usaidwat does not know about.
+    Other(String),
+}
+
+impl fmt::Display for RemovalCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemovalCategory::Moderator => write!(f, "moderator"),
+            RemovalCategory::AutomodFiltered => write!(f, "automod_filtered"),
+            RemovalCategory::Author => write!(f, "author"),
+            RemovalCategory::Deleted => write!(f, "deleted"),
+            RemovalCategory::Reddit => write!(f, "reddit"),
+            RemovalCategory::AntiEvilOps => write!(f, "anti_evil_ops"),
+            RemovalCategory::CommunityOps => write!(f, "community_ops"),
+            RemovalCategory::ContentTakedown => write!(f, "content_takedown"),
+            RemovalCategory::CopyrightTakedown => write!(f, "copyright_takedown"),
+            RemovalCategory::Other(category) => write!(f, "{category}"),
+        }
+    }
+}
+
+impl Serialize for RemovalCategory {
+    /// Serializes the category as Reddit reports it, like `"moderator"`.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A single page of a listing returned by the Reddit API.
+///
+/// Reddit returns listings, such as a user's comments, a page at a time.
+/// The [`after`](Listing::after) cursor identifies the next page, if any.
+#[derive(Debug)]
+pub(crate) struct Listing<T> {
+    items: Vec<T>,
+    after: Option<String>,
+}
+
+impl<T> Listing<T> {
+    /// The items on this page.
+    pub(crate) fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub(crate) fn after(&self) -> Option<&str> {
+        self.after.as_deref()
+    }
 }
 
 impl User {
+    /// Creates a user from data that has already been parsed.
+    pub(crate) fn new(about: About, comments: Vec<Comment>, submissions: Vec<Submission>) -> Self {
+        User {
+            about,
+            comments,
+            submissions,
+        }
+    }
+
     /// Parses text responses from the Reddit API into the associated
     /// data structures.
     ///
@@ -167,11 +472,7 @@ impl User {
         let about = About::parse(user_data.as_ref())?;
         let comments = Comment::parse(comment_data.as_ref())?;
         let submissions = Submission::parse(post_data.as_ref())?;
-        Ok(User {
-            about,
-            comments,
-            submissions,
-        })
+        Ok(User::new(about, comments, submissions))
     }
 
     /// Returns account data for the user.
@@ -188,6 +489,24 @@ impl User {
     pub fn submissions(&self) -> impl Iterator<Item = &Submission> {
         self.submissions.iter()
     }
+
+    /// Drops the user's comments and submissions that have been deleted
+    /// or removed.
+    pub(crate) fn without_removed(self) -> Self {
+        User {
+            comments: self
+                .comments
+                .into_iter()
+                .filter(|c| !c.is_removed())
+                .collect(),
+            submissions: self
+                .submissions
+                .into_iter()
+                .filter(|s| !s.is_removed())
+                .collect(),
+            ..self
+        }
+    }
 }
 
 impl About {
@@ -197,10 +516,25 @@ impl About {
     /// `/users/<user>/about.json`.
     ///
     /// This method is generally invoked by `User`, not directly.
-    fn parse(user_data: &str) -> anyhow::Result<Self> {
+    pub(crate) fn parse(user_data: &str) -> anyhow::Result<Self> {
         Ok(serde_json::from_str(user_data).map(|wrapper: AboutResponse| wrapper.data)?)
     }
 
+    /// True if `user_data` describes a suspended account.
+    ///
+    /// Reddit returns little more than the username for suspended accounts,
+    /// so this should be checked before the data is parsed.
+    pub(crate) fn is_suspended(user_data: &str) -> bool {
+        serde_json::from_str(user_data)
+            .is_ok_and(|wrapper: SuspensionResponse| wrapper.data.is_suspended)
+    }
+
+    /// True if the user has earned karma beyond what every account starts
+    /// with, which means they have posted something at some point.
+    pub(crate) fn has_karma(&self) -> bool {
+        self.link_karma > STARTING_LINK_KARMA || self.comment_karma > 0
+    }
+
     /// The date on which the account was created.
     pub fn created_utc(&self) -> DateTime<Utc> {
         self.created_utc
@@ -215,6 +549,83 @@ impl About {
     pub fn comment_karma(&self) -> i64 {
         self.comment_karma
     }
+
+    /// User's total karma, including karma from awards.
+    ///
+    /// If Reddit does not report it, this is the sum of the user's link
+    /// and comment karma.
+    pub fn total_karma(&self) -> i64 {
+        self.total_karma
+            .unwrap_or(self.link_karma + self.comment_karma)
+    }
+
+    /// Karma the user has earned by receiving awards.
+    pub fn awardee_karma(&self) -> i64 {
+        self.awardee_karma
+    }
+
+    /// Karma the user has earned by giving awards.
+    pub fn awarder_karma(&self) -> i64 {
+        self.awarder_karma
+    }
+
+    /// True if Reddit has verified the account.
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    /// True if the user has verified their email address.
+    pub fn has_verified_email(&self) -> bool {
+        self.has_verified_email
+    }
+
+    /// True if the user moderates at least one subreddit.
+    pub fn is_mod(&self) -> bool {
+        self.is_mod
+    }
+
+    /// True if the user subscribes to Reddit Premium.
+    pub fn has_premium(&self) -> bool {
+        self.is_gold
+    }
+
+    /// True if the user is a Reddit employee.
+    pub fn is_employee(&self) -> bool {
+        self.is_employee
+    }
+
+    /// The URL of the user's avatar, if they have one.
+    ///
+    /// HTML entities in the URL will be converted.
+    pub fn icon_url(&self) -> Option<String> {
+        Some(&self.icon_img)
+            .filter(|url| !url.is_empty())
+            .map(text::convert_html_entities)
+    }
+
+    /// The title of the user's profile, if they have set one.
+    pub fn profile_title(&self) -> Option<&str> {
+        self.profile()
+            .map(|profile| profile.title.trim())
+            .filter(|title| !title.is_empty())
+    }
+
+    /// The public description on the user's profile, if they have written
+    /// one.
+    pub fn description(&self) -> Option<&str> {
+        self.profile()
+            .map(|profile| profile.public_description.trim())
+            .filter(|description| !description.is_empty())
+    }
+
+    /// True if the user has marked their profile NSFW.
+    pub fn is_nsfw(&self) -> bool {
+        self.profile().is_some_and(|profile| profile.over_18)
+    }
+
+    fn profile(&self) -> Option<&Profile> {
+        self.subreddit.as_ref()
+    }
 }
 
 impl Comment {
@@ -225,14 +636,21 @@ impl Comment {
     ///
     /// This method is generally invoked by `User`, not directly.
     fn parse(comment_data: &str) -> anyhow::Result<Vec<Self>> {
+        Ok(Self::parse_page(comment_data)?.into_items())
+    }
+
+    /// Parses a single page of comments, along with the cursor for the
+    /// next page.
+    pub(crate) fn parse_page(comment_data: &str) -> anyhow::Result<Listing<Self>> {
         let json_object = serde_json::from_str(comment_data).map(
-            |comment_listing: ListingResponse<CommentResponse>| {
-                comment_listing
+            |comment_listing: ListingResponse<CommentResponse>| Listing {
+                items: comment_listing
                     .data
                     .children
                     .into_iter()
                     .map(|comment_wrapper| comment_wrapper.data)
-                    .collect()
+                    .collect(),
+                after: comment_listing.data.after,
             },
         )?;
         Ok(json_object)
@@ -240,17 +658,21 @@ impl Comment {
 
     /// The full URL at which the comment can be retrieved.
     pub fn permalink(&self) -> String {
-        self.link_id.split("_").last().map(|link_id| {
-            // Reddit itself uses the submission title here, but in practice,
-            // it can be anything. Since a Comment doesn't have a link back
-            // to its submission, we'll just use a placeholder instead of
-            // the submission title.
-            let placeholder = String::from("z");
-
-            let subreddit = self.subreddit();
-            let comment_id = &self.id;
-            format!("https://www.reddit.com/r/{subreddit}/comments/{link_id}/{placeholder}/{comment_id}")
-        }).unwrap_or(String::from("?"))
+        self.link_id
+            .split("_")
+            .last()
+            .map(|link_id| {
+                // Reddit itself uses the submission title here, but in practice,
+                // it can be anything. Since a Comment doesn't have a link back
+                // to its submission, we'll just use a placeholder instead of
+                // the submission title.
+                let placeholder = String::from("z");
+
+                let subreddit = self.subreddit();
+                let comment_id = &self.id;
+                format!("{REDDIT_URL}/r/{subreddit}/comments/{link_id}/{placeholder}/{comment_id}")
+            })
+            .unwrap_or(String::from("?"))
     }
 
     /// The title of the link for which the comment was posted.
@@ -264,6 +686,105 @@ impl Comment {
     pub fn score(&self) -> i64 {
         self.score
     }
+
+    /// The comment's fullname, which identifies it across Reddit, like
+    /// `t1_lomc1at`.
+    pub fn fullname(&self) -> &str {
+        &self.name
+    }
+
+    /// The time the comment was last edited, or `None` if it has never
+    /// been edited or Reddit does not say when it was.
+    pub fn edited(&self) -> Option<DateTime<Utc>> {
+        self.edited.time()
+    }
+
+    /// True if the comment has been edited.
+    pub fn is_edited(&self) -> bool {
+        self.edited != Edited::Never
+    }
+
+    /// The username of the comment's author.
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    /// The fullname of the comment this comment replied to, or `None` if
+    /// it replied directly to a post.
+    pub fn parent_comment_id(&self) -> Option<&str> {
+        Some(self.parent_id.as_str()).filter(|parent_id| parent_id.starts_with("t1_"))
+    }
+
+    /// The fullname of the comment or post this comment replied to.
+    pub fn parent_id(&self) -> &str {
+        &self.parent_id
+    }
+
+    /// True if the comment has received many upvotes and many downvotes.
+    pub fn is_controversial(&self) -> bool {
+        self.controversiality > 0
+    }
+
+    /// The number of awards the comment has received.
+    pub fn gilded(&self) -> u64 {
+        self.gilded
+    }
+
+    /// The role in which the comment was posted, or `None` if the author
+    /// did not distinguish it.
+    pub fn distinguished(&self) -> Option<Distinguished> {
+        self.distinguished
+    }
+
+    /// True if the comment is pinned to the top of its post.
+    pub fn is_stickied(&self) -> bool {
+        self.stickied
+    }
+
+    /// True if the comment's author also wrote the post it belongs to.
+    pub fn is_submitter(&self) -> bool {
+        self.is_submitter
+    }
+
+    /// True if the post the comment belongs to is marked NSFW.
+    pub fn is_nsfw(&self) -> bool {
+        self.over_18
+    }
+
+    /// The username of the author of the post the comment belongs to.
+    ///
+    /// This is empty for comments retrieved by ID rather than from a
+    /// user's listing.
+    pub fn link_author(&self) -> &str {
+        &self.link_author
+    }
+
+    /// The number of comments on the post the comment belongs to, or `None`
+    /// for comments retrieved by ID rather than from a user's listing.
+    pub fn num_comments(&self) -> Option<u64> {
+        self.num_comments
+    }
+
+    /// True if the comment has the given `flag`.
+    pub fn has_flag(&self, flag: CommentFlag) -> bool {
+        match flag {
+            CommentFlag::Edited => self.is_edited(),
+            CommentFlag::Op => self.is_submitter,
+            CommentFlag::Mod => self.distinguished == Some(Distinguished::Moderator),
+            CommentFlag::Nsfw => self.over_18,
+            CommentFlag::Controversial => self.is_controversial(),
+            CommentFlag::Gilded => self.gilded > 0,
+            CommentFlag::Stickied => self.stickied,
+        }
+    }
+
+    /// All of the flags the comment has.
+    pub fn flags(&self) -> Vec<CommentFlag> {
+        CommentFlag::ALL
+            .into_iter()
+            .filter(|flag| self.has_flag(*flag))
+            .collect()
+    }
 }
 
 impl HasBody for Comment {
@@ -286,12 +807,59 @@ impl HasSubreddit for Comment {
     }
 }
 
+impl Removable for Comment {
+    /// True if the comment's body has been replaced by Reddit's placeholder
+    /// for deleted or removed comments.
+    fn is_removed(&self) -> bool {
+        let body = self.body.trim();
+        body == "[deleted]" || body == "[removed]"
+    }
+}
+
 impl Searchable for Comment {
     fn search_text(&self) -> String {
         self.body()
     }
 }
 
+impl Serialize for Comment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedComment::serialize(self, serializer)
+    }
+}
+
+/// The fields of a [serialized](self#serialization) comment, which include
+/// its permalink, although Reddit does not report it in full.
+#[derive(Serialize)]
+#[serde(remote = "Comment")]
+#[allow(dead_code)]
+struct SerializedComment {
+    id: String,
+    name: String,
+    #[serde(getter = "Comment::permalink")]
+    permalink: String,
+    author: String,
+    parent_id: String,
+    subreddit_id: String,
+    subreddit: String,
+    link_title: String,
+    link_id: String,
+    created_utc: DateTime<Utc>,
+    body: String,
+    edited: Edited,
+    ups: i64,
+    downs: i64,
+    score: i64,
+    controversiality: u8,
+    gilded: u64,
+    distinguished: Option<Distinguished>,
+    stickied: bool,
+    is_submitter: bool,
+    over_18: bool,
+    link_author: String,
+    num_comments: Option<u64>,
+}
+
 impl Submission {
     /// Parses a text response from the Reddit API into a list of
     /// submissions (posts).
@@ -301,14 +869,21 @@ impl Submission {
     ///
     /// This method is generally invoked by `User`, not directly.
     fn parse(post_data: &str) -> anyhow::Result<Vec<Self>> {
+        Ok(Self::parse_page(post_data)?.into_items())
+    }
+
+    /// Parses a single page of submissions, along with the cursor for the
+    /// next page.
+    pub(crate) fn parse_page(post_data: &str) -> anyhow::Result<Listing<Self>> {
         let json_object = serde_json::from_str(post_data).map(
-            |comment_listing: ListingResponse<SubmissionResponse>| {
-                comment_listing
+            |comment_listing: ListingResponse<SubmissionResponse>| Listing {
+                items: comment_listing
                     .data
                     .children
                     .into_iter()
                     .map(|comment_wrapper| comment_wrapper.data)
-                    .collect()
+                    .collect(),
+                after: comment_listing.data.after,
             },
         )?;
         Ok(json_object)
@@ -327,7 +902,7 @@ impl Submission {
     /// The submission's permalink.
     pub fn permalink(&self) -> String {
         let path = &self.permalink;
-        format!("https://www.reddit.com{path}")
+        format!("{REDDIT_URL}{path}")
     }
 
     /// The submission's title.
@@ -339,6 +914,95 @@ impl Submission {
     pub fn url(&self) -> &str {
         &self.url
     }
+
+    /// The submission's total score.
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// The submission's fullname, which identifies it across Reddit, like
+    /// `t3_2au1ki`.
+    pub fn fullname(&self) -> &str {
+        &self.name
+    }
+
+    /// The time the submission was last edited, or `None` if it has never
+    /// been edited or Reddit does not say when it was.
+    pub fn edited(&self) -> Option<DateTime<Utc>> {
+        self.edited.time()
+    }
+
+    /// True if the submission has been edited.
+    pub fn is_edited(&self) -> bool {
+        self.edited != Edited::Never
+    }
+
+    /// The number of comments on the submission.
+    pub fn num_comments(&self) -> u64 {
+        self.num_comments
+    }
+
+    /// The text of the submission's flair, if it has any.
+    pub fn flair(&self) -> Option<&str> {
+        self.link_flair_text
+            .as_deref()
+            .filter(|flair| !flair.is_empty())
+    }
+
+    /// The fraction of votes on the submission that are upvotes, or `None`
+    /// if it is not known.
+    pub fn upvote_ratio(&self) -> Option<f64> {
+        self.upvote_ratio
+    }
+
+    /// True if the submission is marked NSFW.
+    pub fn is_nsfw(&self) -> bool {
+        self.over_18
+    }
+
+    /// True if the submission is marked as a spoiler.
+    pub fn is_spoiler(&self) -> bool {
+        self.spoiler
+    }
+
+    /// True if the submission is locked, so no one can comment on it.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// True if the submission is a video hosted by Reddit.
+    pub fn is_video(&self) -> bool {
+        self.is_video
+    }
+
+    /// The kind of content the submission links to.
+    ///
+    /// Reddit does not always say; submissions that it does not describe
+    /// are assumed to be text if they are self posts and links otherwise.
+    pub fn media_type(&self) -> MediaType {
+        match self.post_hint.as_deref() {
+            _ if self.is_video => MediaType::Video,
+            Some("image") => MediaType::Image,
+            Some("hosted:video") => MediaType::Video,
+            Some("rich:video") => MediaType::Embed,
+            Some("self") => MediaType::Text,
+            Some("link") => MediaType::Link,
+            _ if self.is_self() => MediaType::Text,
+            _ => MediaType::Link,
+        }
+    }
+
+    /// The number of times the submission has been crossposted to other
+    /// subreddits.
+    pub fn num_crossposts(&self) -> u64 {
+        self.num_crossposts
+    }
+
+    /// Who removed the submission, or why, or `None` if it has not been
+    /// removed.
+    pub fn removed_by_category(&self) -> Option<&RemovalCategory> {
+        self.removed_by_category.as_ref()
+    }
 }
 
 impl HasBody for Submission {
@@ -362,8 +1026,14 @@ impl HasSubreddit for Submission {
 }
 
 impl Removable for Submission {
+    /// True if the submission has been removed or deleted.
+    ///
+    /// Submissions from sources that do not say why they were removed,
+    /// like Reddit's data export, and self posts deleted by their authors
+    /// are removed if their text says so.
     fn is_removed(&self) -> bool {
-        self.selftext.to_lowercase() == "[removed]"
+        let selftext = self.selftext.trim().to_lowercase();
+        self.removed_by_category.is_some() || selftext == "[deleted]" || selftext == "[removed]"
     }
 }
 
@@ -380,15 +1050,149 @@ impl Searchable for Submission {
 // Deserializers
 // --------------------------------------------------------------------------
 
-fn from_timestamp_f64<'de, D>(deserializer: D) -> std::result::Result<DateTime<Utc>, D::Error>
+/// A time, either as a Unix timestamp, as Reddit reports it, or as an
+/// ISO-8601 string, as things are serialized.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Timestamp {
+    Unix(f64),
+    Iso8601(DateTime<Utc>),
+}
+
+impl Timestamp {
+    fn into_datetime<E: serde::de::Error>(self) -> std::result::Result<DateTime<Utc>, E> {
+        match self {
+            Timestamp::Unix(ts_f64) => f64_to_i64(ts_f64)
+                .and_then(|ts| DateTime::from_timestamp(ts, 0))
+                .ok_or_else(|| E::custom(format!("Invalid Unix timestamp: {ts_f64}"))),
+            Timestamp::Iso8601(datetime) => Ok(datetime),
+        }
+    }
+}
+
+fn from_timestamp<'de, D>(deserializer: D) -> std::result::Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let ts_f64 = f64::deserialize(deserializer)?;
-    let ts = f64_to_i64(ts_f64)
-        .ok_or_else(|| serde::de::Error::custom(format!("Invalid Unix timestamp: {ts_f64}")))?;
-    DateTime::from_timestamp(ts, 0)
-        .ok_or_else(|| serde::de::Error::custom(format!("Invalid Unix timestamp: {ts}")))
+    Timestamp::deserialize(deserializer)?.into_datetime()
+}
+
+/// When a comment or submission was last edited.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Edited {
+    /// The thing has never been edited.
+    #[default]
+    Never,
+
+    /// The thing has been edited, but Reddit does not say when, as is the
+    /// case for some older things.
+    Unknown,
+
+    /// The thing was last edited at the given time.
+    At(DateTime<Utc>),
+}
+
+impl Edited {
+    fn time(self) -> Option<DateTime<Utc>> {
+        match self {
+            Edited::At(edited) => Some(edited),
+            Edited::Never | Edited::Unknown => None,
+        }
+    }
+}
+
+impl Serialize for Edited {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Edited::Never => serializer.serialize_none(),
+            Edited::Unknown => serializer.serialize_bool(true),
+            Edited::At(edited) => edited.serialize(serializer),
+        }
+    }
+}
+
+/// Reddit reports `false` for things that have never been edited, and the
+/// time of the last edit otherwise, although some older things are only
+/// reported as `true`. Serialized things use `null` instead of `false`.
+fn from_edited<'de, D>(deserializer: D) -> std::result::Result<Edited, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawEdited {
+        Flag(bool),
+        Timestamp(Timestamp),
+    }
+
+    match Option::<RawEdited>::deserialize(deserializer)? {
+        None | Some(RawEdited::Flag(false)) => Ok(Edited::Never),
+        Some(RawEdited::Flag(true)) => Ok(Edited::Unknown),
+        Some(RawEdited::Timestamp(ts)) => ts.into_datetime().map(Edited::At),
+    }
+}
+
+/// Reddit reports permalinks as paths, but serialized things have full URLs.
+fn from_permalink<'de, D>(deserializer: D) -> std::result::Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let permalink = String::deserialize(deserializer)?;
+    Ok(match permalink.strip_prefix(REDDIT_URL) {
+        Some(path) => path.to_string(),
+        None => permalink,
+    })
+}
+
+/// Reddit reports `null` for some fields of things that do not have them;
+/// these are treated as the field's default value instead.
+fn from_nullable<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Reddit reports `null` for things that have not been distinguished, and
+/// the role in which they were distinguished otherwise. Unknown roles are
+/// ignored.
+fn from_distinguished<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Distinguished>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let distinguished = Option::<String>::deserialize(deserializer)?;
+    Ok(match distinguished.as_deref() {
+        Some("moderator") => Some(Distinguished::Moderator),
+        Some("admin") => Some(Distinguished::Admin),
+        Some("special") => Some(Distinguished::Special),
+        _ => None,
+    })
+}
+
+/// Reddit reports `null` for things that have not been removed, and who
+/// removed them, or why, otherwise.
+fn from_removed_by_category<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<RemovalCategory>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let category = Option::<String>::deserialize(deserializer)?;
+    Ok(category.map(|category| match category.as_str() {
+        "moderator" => RemovalCategory::Moderator,
+        "automod_filtered" => RemovalCategory::AutomodFiltered,
+        "author" => RemovalCategory::Author,
+        "deleted" => RemovalCategory::Deleted,
+        "reddit" => RemovalCategory::Reddit,
+        "anti_evil_ops" => RemovalCategory::AntiEvilOps,
+        "community_ops" => RemovalCategory::CommunityOps,
+        "content_takedown" => RemovalCategory::ContentTakedown,
+        "copyright_takedown" => RemovalCategory::CopyrightTakedown,
+        _ => RemovalCategory::Other(category),
+    }))
 }
 
 fn f64_to_i64(n: f64) -> Option<i64> {
@@ -399,6 +1203,14 @@ fn f64_to_i64(n: f64) -> Option<i64> {
     }
 }
 
+// Serializers
+// --------------------------------------------------------------------------
+
+/// Serializes a permalink path as a full URL.
+fn to_permalink<S: Serializer>(path: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("{REDDIT_URL}{path}"))
+}
+
 // Response wrappers
 // --------------------------------------------------------------------------
 // These are necessary because the Reddit API returns data wrapped in "data"
@@ -410,6 +1222,17 @@ struct AboutResponse {
     data: About,
 }
 
+#[derive(Debug, Deserialize)]
+struct SuspensionResponse {
+    data: Suspension,
+}
+
+#[derive(Debug, Deserialize)]
+struct Suspension {
+    #[serde(default)]
+    is_suspended: bool,
+}
+
 #[derive(Debug, Deserialize)]
 struct ListingResponse<T> {
     data: ChildrenResponse<T>,
@@ -418,6 +1241,7 @@ struct ListingResponse<T> {
 #[derive(Debug, Deserialize)]
 struct ChildrenResponse<T> {
     children: Vec<T>,
+    after: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -437,7 +1261,7 @@ struct SubmissionResponse {
 mod tests {
     mod about {
         use super::super::*;
-        use crate::test_utils::load_data;
+        use crate::test_utils::{load_data, load_data_with};
 
         #[test]
         fn it_cannot_parse_invalid_data() {
@@ -451,6 +1275,32 @@ mod tests {
             assert!(about.is_ok());
         }
 
+        #[test]
+        fn it_detects_suspended_accounts() {
+            assert!(About::is_suspended(&load_data("about_suspended")));
+            assert!(!About::is_suspended(&load_data("about_mipadi")));
+            assert!(!About::is_suspended(&load_data("about_404")));
+        }
+
+        #[test]
+        fn it_knows_if_the_user_has_karma() {
+            assert!(
+                About::parse(&load_data("about_mipadi"))
+                    .unwrap()
+                    .has_karma()
+            );
+            assert!(!About::parse(&load_data("about_empty")).unwrap().has_karma());
+        }
+
+        #[test]
+        fn it_does_not_count_starting_karma() {
+            let data = load_data_with("about_mipadi", |about| {
+                about[0]["link_karma"] = STARTING_LINK_KARMA.into();
+                about[0]["comment_karma"] = 0.into();
+            });
+            assert!(!About::parse(&data).unwrap().has_karma());
+        }
+
         #[test]
         fn it_parses_fields() {
             let about = About::parse(&load_data("about_mipadi")).unwrap();
@@ -467,11 +1317,61 @@ mod tests {
             assert_eq!(about.link_karma(), 11729);
             assert_eq!(about.comment_karma(), 121995);
         }
+
+        #[test]
+        fn it_parses_account_details() {
+            let about = About::parse(&load_data("about_mipadi")).unwrap();
+            assert_eq!(about.total_karma(), 133724);
+            assert_eq!(about.awardee_karma(), 0);
+            assert_eq!(about.awarder_karma(), 0);
+            assert!(about.is_verified());
+            assert!(about.has_verified_email());
+            assert!(about.is_mod());
+            assert!(!about.has_premium());
+            assert!(!about.is_employee());
+            assert!(!about.is_nsfw());
+            assert_eq!(about.profile_title(), None);
+            assert_eq!(about.description(), None);
+            assert!(
+                about
+                    .icon_url()
+                    .unwrap()
+                    .starts_with("https://styles.redditmedia.com/")
+            );
+        }
+
+        #[test]
+        fn it_parses_a_profile_description() {
+            let data = load_data_with("about_mipadi", |about| {
+                about[0]["subreddit"]["title"] = "mipadi".into();
+                about[0]["subreddit"]["public_description"] = "I write software.\n".into();
+            });
+            let about = About::parse(&data).unwrap();
+            assert_eq!(about.profile_title(), Some("mipadi"));
+            assert_eq!(about.description(), Some("I write software."));
+        }
+
+        #[test]
+        fn it_parses_accounts_without_details() {
+            let about: About = serde_json::from_value(serde_json::json!({
+                "name": "mipadi",
+                "id": "",
+                "created_utc": 1207004126.0,
+                "link_karma": 10,
+                "comment_karma": 20,
+                "verified": null,
+            }))
+            .unwrap();
+            assert_eq!(about.total_karma(), 30);
+            assert!(!about.is_verified());
+            assert_eq!(about.icon_url(), None);
+            assert_eq!(about.description(), None);
+        }
     }
 
     mod comments {
         use super::super::*;
-        use crate::test_utils::{load_data, load_output};
+        use crate::test_utils::{load_data, load_data_with, load_output};
         use chrono::Local;
         use pretty_assertions::assert_eq;
 
@@ -493,6 +1393,20 @@ mod tests {
             assert!(comments.is_ok());
         }
 
+        #[test]
+        fn it_parses_the_next_page_cursor() {
+            let page = Comment::parse_page(&load_data("comments_mipadi")).unwrap();
+            assert_eq!(page.after(), Some("t1_lomc1at"));
+            assert_eq!(page.into_items().len(), 100);
+        }
+
+        #[test]
+        fn it_parses_the_last_page() {
+            let page = Comment::parse_page(&load_data("comments_empty")).unwrap();
+            assert!(page.after().is_none());
+            assert!(page.into_items().is_empty());
+        }
+
         #[test]
         fn it_parses_fields() {
             let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
@@ -540,6 +1454,133 @@ mod tests {
             assert_eq!(comment.score(), -3);
         }
 
+        #[test]
+        fn it_returns_its_fullname() {
+            let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
+            let comment = &comments[9];
+            assert_eq!(comment.fullname(), "t1_mjyuqdz");
+        }
+
+        #[test]
+        fn it_returns_its_parent_comment() {
+            let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
+            assert_eq!(comments[0].parent_comment_id(), None);
+            assert_eq!(comments[3].parent_comment_id(), Some("t1_mlqtxuw"));
+            assert_eq!(comments[3].author(), "mipadi");
+        }
+
+        #[test]
+        fn it_parses_comments_without_a_link_title() {
+            let comments = Comment::parse(&load_data("info_mipadi")).unwrap();
+            assert_eq!(comments.len(), 2);
+            assert_eq!(comments[0].author(), "ThreadReplier");
+            assert_eq!(comments[0].link_title(), "");
+        }
+
+        #[test]
+        fn it_returns_when_it_was_edited() {
+            let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
+            assert_eq!(comments[9].edited(), None);
+            assert_eq!(
+                comments[33].edited(),
+                DateTime::from_timestamp(1734422268, 0)
+            );
+        }
+
+        #[test]
+        fn it_knows_it_was_edited_when_reddit_does_not_say_when() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                comments[9]["edited"] = true.into();
+            });
+            let comments = Comment::parse(&data).unwrap();
+            assert!(comments[9].is_edited());
+            assert_eq!(comments[9].edited(), None);
+            assert_eq!(comments[9].flags(), [CommentFlag::Edited]);
+            assert!(!comments[10].is_edited());
+        }
+
+        #[test]
+        fn it_returns_its_flags() {
+            let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
+            assert!(comments[9].flags().is_empty());
+            assert_eq!(comments[33].flags(), [CommentFlag::Edited]);
+            assert!(comments[68].is_controversial());
+            assert_eq!(comments[68].flags(), [CommentFlag::Controversial]);
+        }
+
+        #[test]
+        fn it_parses_distinguished_comments() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                let comment = &mut comments[0];
+                comment["distinguished"] = "moderator".into();
+                comment["is_submitter"] = true.into();
+                comment["over_18"] = true.into();
+                comment["stickied"] = true.into();
+                comment["gilded"] = 2.into();
+            });
+
+            let comments = Comment::parse(&data).unwrap();
+            let comment = &comments[0];
+            assert_eq!(comment.distinguished(), Some(Distinguished::Moderator));
+            assert!(comment.is_submitter());
+            assert!(comment.is_nsfw());
+            assert!(comment.is_stickied());
+            assert_eq!(comment.gilded(), 2);
+            assert_eq!(
+                comment.flags(),
+                [
+                    CommentFlag::Op,
+                    CommentFlag::Mod,
+                    CommentFlag::Nsfw,
+                    CommentFlag::Gilded,
+                    CommentFlag::Stickied
+                ]
+            );
+        }
+
+        #[test]
+        fn it_does_not_flag_admin_comments_as_mod() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                comments[0]["distinguished"] = "admin".into();
+                comments[1]["distinguished"] = "special".into();
+            });
+
+            let comments = Comment::parse(&data).unwrap();
+            assert_eq!(comments[0].distinguished(), Some(Distinguished::Admin));
+            assert!(!comments[0].has_flag(CommentFlag::Mod));
+            assert_eq!(comments[1].distinguished(), Some(Distinguished::Special));
+            assert!(!comments[1].has_flag(CommentFlag::Mod));
+        }
+
+        #[test]
+        fn it_returns_details_of_its_post() {
+            let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
+            assert_eq!(comments[0].parent_id(), "t3_1k1iixf");
+            assert_eq!(comments[0].link_author(), "crimsonlaw");
+            assert_eq!(comments[0].num_comments(), Some(13));
+        }
+
+        #[test]
+        fn it_parses_comments_without_details_of_their_post() {
+            let comments = Comment::parse(&load_data("info_mipadi")).unwrap();
+            assert_eq!(comments[0].link_author(), "");
+            assert_eq!(comments[0].num_comments(), None);
+            assert_eq!(comments[0].gilded(), 0);
+            assert!(!comments[0].is_nsfw());
+        }
+
+        #[test]
+        fn it_knows_if_it_has_been_removed() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                comments[0]["body"] = "[deleted]".into();
+                comments[1]["body"] = "[removed]".into();
+            });
+            let comments = Comment::parse(&data).unwrap();
+            assert!(comments[0].is_removed());
+            assert!(comments[1].is_removed());
+            assert!(!comments[2].is_removed());
+        }
+
         #[test]
         fn it_returns_its_subreddit() {
             let comments = Comment::parse(&load_data("comments_mipadi")).unwrap();
@@ -708,7 +1749,7 @@ mod tests {
 
     mod submissions {
         use super::super::*;
-        use crate::test_utils::load_data;
+        use crate::test_utils::{load_data, load_data_with};
 
         #[test]
         fn it_cannot_parse_invalid_data() {
@@ -728,6 +1769,20 @@ mod tests {
             assert!(submissions.is_ok());
         }
 
+        #[test]
+        fn it_parses_the_next_page_cursor() {
+            let page = Submission::parse_page(&load_data("submitted_mipadi")).unwrap();
+            assert_eq!(page.after(), Some("t3_2au1ki"));
+            assert_eq!(page.into_items().len(), 100);
+        }
+
+        #[test]
+        fn it_parses_the_last_page() {
+            let page = Submission::parse_page(&load_data("submitted_empty")).unwrap();
+            assert!(page.after().is_none());
+            assert!(page.into_items().is_empty());
+        }
+
         #[test]
         fn it_parses_fields() {
             let submissions = Submission::parse(&load_data("submitted_mipadi")).unwrap();
@@ -1007,10 +2062,188 @@ Membrane bring any extra benefits?";
             assert_eq!(submission.created_utc(), expected);
         }
 
+        #[test]
+        fn it_returns_its_details() {
+            let submissions = Submission::parse(&load_data("submitted_mipadi")).unwrap();
+            let submission = &submissions[0];
+            assert_eq!(submission.flair(), Some("Discussion"));
+            assert_eq!(submission.upvote_ratio(), Some(0.83));
+            assert_eq!(submission.num_comments(), 22);
+            assert_eq!(submission.media_type(), MediaType::Link);
+            assert!(!submission.is_nsfw());
+            assert!(!submission.is_spoiler());
+            assert!(!submission.is_locked());
+            assert!(!submission.is_video());
+            assert_eq!(submissions[4].flair(), None);
+            assert_eq!(submissions[4].media_type(), MediaType::Text);
+            assert!(submissions[18].is_locked());
+            assert_eq!(submissions[42].num_crossposts(), 3);
+        }
+
+        #[test]
+        fn it_returns_who_removed_it() {
+            let submissions = Submission::parse(&load_data("submitted_mipadi")).unwrap();
+            assert_eq!(submissions[0].removed_by_category(), None);
+            assert!(!submissions[0].is_removed());
+            assert_eq!(
+                submissions[13].removed_by_category(),
+                Some(&RemovalCategory::Moderator)
+            );
+            // Link posts are removed even though they have no "[removed]" text.
+            assert!(submissions[13].is_removed());
+            assert!(submissions[12].is_removed());
+        }
+
+        #[test]
+        fn it_parses_unknown_removal_categories() {
+            let data = load_data_with("submitted_mipadi", |posts| {
+                posts[0]["removed_by_category"] = "spam_filter".into();
+            });
+            let submissions = Submission::parse(&data).unwrap();
+            assert_eq!(
+                submissions[0].removed_by_category(),
+                Some(&RemovalCategory::Other(String::from("spam_filter")))
+            );
+            assert!(submissions[0].is_removed());
+        }
+
+        #[test]
+        fn it_knows_if_it_has_been_deleted_by_its_author() {
+            let data = load_data_with("submitted_mipadi", |posts| {
+                posts[0]["selftext"] = "[deleted]".into();
+            });
+            let submissions = Submission::parse(&data).unwrap();
+            assert!(submissions[0].is_removed());
+            assert!(!submissions[1].is_removed());
+        }
+
+        #[test]
+        fn it_parses_media_types() {
+            let data = load_data_with("submitted_mipadi", |posts| {
+                posts[0]["post_hint"] = "image".into();
+                posts[1]["post_hint"] = "rich:video".into();
+                posts[2]["is_video"] = true.into();
+            });
+            let submissions = Submission::parse(&data).unwrap();
+            assert_eq!(submissions[0].media_type(), MediaType::Image);
+            assert_eq!(submissions[1].media_type(), MediaType::Embed);
+            assert_eq!(submissions[2].media_type(), MediaType::Video);
+        }
+
         #[test]
         fn it_returns_an_empty_collection() {
             let submissions = Submission::parse(&load_data("submitted_empty")).unwrap();
             assert!(submissions.is_empty());
         }
     }
+
+    mod serialization {
+        use super::super::*;
+        use crate::test_utils::load_data_with;
+        use pretty_assertions::assert_eq;
+        use serde_json::{Value, json};
+
+        /// Wraps serialized things in the format that the Reddit API
+        /// returns listings in.
+        fn listing(kind: &str, things: &Value) -> String {
+            let children: Vec<_> = things
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|thing| json!({"kind": kind, "data": thing}))
+                .collect();
+            json!({"kind": "Listing", "data": {"after": null, "children": children}}).to_string()
+        }
+
+        #[test]
+        fn it_serializes_comments() {
+            let user = User::test();
+            let comment = serde_json::to_value(user.comments().next().unwrap()).unwrap();
+            assert_eq!(comment["id"], "mnpd3zh");
+            assert_eq!(comment["name"], "t1_mnpd3zh");
+            assert_eq!(comment["created_utc"], "2025-04-18T03:44:41Z");
+            assert_eq!(comment["edited"], Value::Null);
+            assert_eq!(comment["over_18"], false);
+            assert_eq!(
+                comment["permalink"],
+                "https://www.reddit.com/r/cyphersystem/comments/1k1iixf/z/mnpd3zh"
+            );
+        }
+
+        #[test]
+        fn it_serializes_submissions() {
+            let user = User::test();
+            let submission = serde_json::to_value(user.submissions().next().unwrap()).unwrap();
+            assert_eq!(submission["name"], "t3_1hv9k9l");
+            assert_eq!(submission["created_utc"], "2025-01-06T20:54:01Z");
+            assert_eq!(submission["link_flair_text"], "Discussion");
+            assert_eq!(submission["over_18"], false);
+            assert_eq!(
+                submission["permalink"],
+                "https://www.reddit.com/r/rpg/comments/1hv9k9l/collections_coinage_and_the_tyranny_of_fantasy/"
+            );
+        }
+
+        #[test]
+        fn it_serializes_account_data() {
+            let about = serde_json::to_value(User::test().about()).unwrap();
+            assert_eq!(about["name"], "mipadi");
+            assert_eq!(about["created_utc"], "2008-03-31T22:55:26Z");
+            assert_eq!(about["link_karma"], 11729);
+            assert!(about["is_gold"].is_boolean());
+            assert!(about["subreddit"].is_object());
+        }
+
+        #[test]
+        fn it_round_trips_through_reddit_responses() {
+            let expected = serde_json::to_value(User::test()).unwrap();
+            let user = User::parse(
+                json!({"kind": "t2", "data": expected["about"]}).to_string(),
+                listing("t1", &expected["comments"]),
+                listing("t3", &expected["submissions"]),
+            )
+            .unwrap();
+            assert_eq!(serde_json::to_value(user).unwrap(), expected);
+        }
+
+        #[test]
+        fn it_round_trips_users() {
+            let expected = serde_json::to_value(User::test()).unwrap();
+            let user: User = serde_json::from_value(expected.clone()).unwrap();
+            assert_eq!(user.comments().count(), 100);
+            assert_eq!(serde_json::to_value(user).unwrap(), expected);
+        }
+
+        #[test]
+        fn it_parses_iso_8601_timestamps() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                comments[0]["created_utc"] = "2025-04-18T03:44:41Z".into();
+                comments[0]["edited"] = "2025-04-19T00:00:00Z".into();
+                comments[1]["edited"] = Value::Null;
+            });
+            let comments = Comment::parse(&data).unwrap();
+            assert_eq!(
+                comments[0].created_utc(),
+                DateTime::from_timestamp(1744947881, 0).unwrap()
+            );
+            assert_eq!(
+                comments[0].edited(),
+                Some(DateTime::from_timestamp(1745020800, 0).unwrap())
+            );
+            assert_eq!(comments[1].edited(), None);
+        }
+
+        #[test]
+        fn it_round_trips_things_edited_at_an_unknown_time() {
+            let data = load_data_with("comments_mipadi", |comments| {
+                comments[0]["edited"] = true.into();
+            });
+            let comment = Comment::parse(&data).unwrap().remove(0);
+            let serialized = serde_json::to_value(&comment).unwrap();
+            assert_eq!(serialized["edited"], true);
+            let comment: Comment = serde_json::from_value(serialized).unwrap();
+            assert!(comment.is_edited());
+            assert_eq!(comment.edited(), None);
+        }
+    }
 }
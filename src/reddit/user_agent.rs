@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+// Copyright (C) 2026 Michael Dippery <michael@monkey-robot.com>
+
+//! The User-Agent that identifies usaidwat to Reddit.
+//!
+//! Reddit's [API rules] require every client to send a unique and
+//! descriptive User-Agent of the form
+//! `<platform>:<app ID>:<version> (by /u/<reddit username>)`, where the
+//! username belongs to the person operating the app. Since every OAuth app
+//! is operated by someone different, the app name and contact username can
+//! be set in the user's [configuration file](crate::config):
+//!
+//! ```toml
+//! [reddit]
+//! app_name = "my-usaidwat"
+//! contact = "my reddit username"
+//! ```
+//!
+//! [API rules]: https://github.com/reddit-archive/reddit/wiki/API
+
+use crate::config::{ConfigFile, RedditConfig};
+use anyhow::anyhow;
+use regex::Regex;
+use std::str::FromStr;
+use std::{env, fmt};
+
+/// The app name used if none is configured.
+pub const DEFAULT_APP_NAME: &str = env!("CARGO_PKG_NAME");
+
+/// The contact username used if none is configured.
+pub const DEFAULT_CONTACT: &str = "mipadi";
+
+/// A User-Agent in the format that Reddit requires.
+///
+/// # Examples
+///
+/// ```
+/// use usaidwat::reddit::user_agent::UserAgent;
+/// let ua: UserAgent = "linux:usaidwat:v1.0.0 (by /u/mipadi)".parse().unwrap();
+/// assert_eq!(ua.app_name(), "usaidwat");
+/// assert_eq!(ua.contact(), "mipadi");
+/// assert!("usaidwat/1.0.0".parse::<UserAgent>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserAgent {
+    platform: String,
+    app_name: String,
+    version: String,
+    contact: String,
+}
+
+impl Default for UserAgent {
+    /// The User-Agent of the official app, running on the current platform.
+    fn default() -> Self {
+        Self::new(DEFAULT_APP_NAME, DEFAULT_CONTACT).expect("default User-Agent should be valid")
+    }
+}
+
+impl UserAgent {
+    /// Creates a User-Agent for the app named `app_name`, operated by the
+    /// Reddit user `contact`, running this version of usaidwat on the
+    /// current platform.
+    ///
+    /// Returns an error if the resulting User-Agent would not match
+    /// Reddit's required format; for example, if the app name contains
+    /// spaces or colons, or `contact` is not a valid Reddit username.
+    pub fn new(app_name: &str, contact: &str) -> anyhow::Result<Self> {
+        let contact = contact
+            .trim_start_matches('/')
+            .trim_start_matches("u/")
+            .to_string();
+        format!(
+            "{}:{app_name}:v{} (by /u/{contact})",
+            env::consts::OS,
+            env!("CARGO_PKG_VERSION")
+        )
+        .parse()
+    }
+
+    /// Creates the User-Agent configured in the `[reddit]` section of the
+    /// user's configuration file, using the defaults for anything that is
+    /// not set.
+    pub fn from_config(config: &RedditConfig) -> anyhow::Result<Self> {
+        let app_name = config.app_name().unwrap_or(DEFAULT_APP_NAME);
+        let contact = config.contact().unwrap_or(DEFAULT_CONTACT);
+        Self::new(app_name, contact)
+            .map_err(|err| anyhow!("invalid app_name or contact in configuration file: {err}"))
+    }
+
+    /// Loads the User-Agent configured in the user's configuration file.
+    ///
+    /// If the configuration file cannot be loaded, the default User-Agent
+    /// is returned; the error is reported when credentials are loaded.
+    /// Returns an error only if the configured User-Agent is invalid.
+    pub fn load() -> anyhow::Result<Self> {
+        match ConfigFile::load() {
+            Ok(config) => Self::from_config(config.reddit()),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// The platform the app runs on, like "linux".
+    pub fn platform(&self) -> &str {
+        &self.platform
+    }
+
+    /// The name of the app.
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// The app's version, like "v1.0.0".
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The Reddit username of the person operating the app.
+    pub fn contact(&self) -> &str {
+        &self.contact
+    }
+}
+
+impl FromStr for UserAgent {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let re = Regex::new(r"^([^:\s]+):([^:\s]+):([^:\s]+) \(by /u/([A-Za-z0-9_-]{3,20})\)$")
+            .expect("User-Agent regex should be valid");
+        let captures = re.captures(s).ok_or_else(|| {
+            anyhow!("User-Agent must look like \"platform:app:version (by /u/username)\": {s}")
+        })?;
+        Ok(Self {
+            platform: captures[1].to_string(),
+            app_name: captures[2].to_string(),
+            version: captures[3].to_string(),
+            contact: captures[4].to_string(),
+        })
+    }
+}
+
+impl fmt::Display for UserAgent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{} (by /u/{})",
+            self.platform, self.app_name, self.version, self.contact
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn it_creates_a_user_agent_in_reddits_format() {
+        let ua = UserAgent::new("my-usaidwat", "someone").unwrap();
+        assert_eq!(
+            ua.to_string(),
+            format!(
+                "{}:my-usaidwat:v{} (by /u/someone)",
+                env::consts::OS,
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+    }
+
+    #[test]
+    fn it_accepts_prefixed_contacts() {
+        assert_eq!(
+            UserAgent::new("app", "u/someone").unwrap().contact(),
+            "someone"
+        );
+        assert_eq!(
+            UserAgent::new("app", "/u/someone").unwrap().contact(),
+            "someone"
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_app_names() {
+        assert!(UserAgent::new("", "someone").is_err());
+        assert!(UserAgent::new("my app", "someone").is_err());
+        assert!(UserAgent::new("my:app", "someone").is_err());
+    }
+
+    #[test]
+    fn it_rejects_invalid_contacts() {
+        assert!(UserAgent::new("app", "").is_err());
+        assert!(UserAgent::new("app", "no").is_err());
+        assert!(UserAgent::new("app", "not a username").is_err());
+        assert!(UserAgent::new("app", "abcdefghijklmnopqrstu").is_err());
+    }
+
+    #[test]
+    fn it_parses_user_agents() {
+        let ua: UserAgent = "macos:usaidwat:v2.1.0 (by /u/mipadi)".parse().unwrap();
+        assert_eq!(ua.platform(), "macos");
+        assert_eq!(ua.app_name(), "usaidwat");
+        assert_eq!(ua.version(), "v2.1.0");
+        assert_eq!(ua.contact(), "mipadi");
+        assert!("usaidwat/2.1.0 by u/mipadi".parse::<UserAgent>().is_err());
+    }
+
+    #[test]
+    fn it_uses_configured_settings() {
+        let config = ConfigFile::parse(indoc! {r#"
+            [reddit]
+            app_name = "corp-usaidwat"
+            contact = "someone"
+        "#})
+        .unwrap();
+        let ua = UserAgent::from_config(config.reddit()).unwrap();
+        assert_eq!(ua.app_name(), "corp-usaidwat");
+        assert_eq!(ua.contact(), "someone");
+    }
+
+    #[test]
+    fn it_uses_defaults_for_missing_settings() {
+        let config = ConfigFile::parse("").unwrap();
+        let ua = UserAgent::from_config(config.reddit()).unwrap();
+        assert_eq!(ua, UserAgent::default());
+        assert_eq!(ua.app_name(), DEFAULT_APP_NAME);
+        assert_eq!(ua.contact(), DEFAULT_CONTACT);
+    }
+}
@@ -42,6 +42,16 @@ where
         }
     }
 
+    /// Summarizes content from `user` instead, with the same client and
+    /// model, so that one client can be used to summarize several users.
+    pub fn user<'b>(self, user: &'b Redditor) -> Summarizer<'b, C> {
+        Summarizer {
+            client: self.client,
+            user,
+            model: self.model,
+        }
+    }
+
     /// Sets the AI model used for summarization.
     ///
     /// By default, the summarizer uses the default model, but that option can
@@ -284,6 +294,17 @@ mod tests {
         assert_eq!(summarizer.model, TestAIModel::OtherAIModel);
     }
 
+    #[tokio::test]
+    async fn it_summarizes_another_user_with_the_same_model() {
+        let redditor = Redditor::test().await;
+        let other = Redditor::test_empty().await;
+        let summarizer = Summarizer::test(&redditor)
+            .model(TestAIModel::OtherAIModel)
+            .user(&other);
+        assert_eq!(summarizer.model, TestAIModel::OtherAIModel);
+        assert_eq!(summarizer.context(false), "");
+    }
+
     #[tokio::test]
     async fn it_provides_context_for_an_llm() {
         let redditor = Redditor::test().await;
@@ -2,9 +2,14 @@
 // Copyright (C) 2025 Michael Dippery <michael@monkey-robot.com>
 
 use crate::reddit::Redditor;
+use crate::reddit::client::ListingOptions;
+use crate::reddit::resource::Resource;
 use crate::reddit::service::Service;
+use crate::reddit::thing::User;
 use hypertyper::prelude::*;
+use serde_json::{Value, json};
 use std::fs;
+use std::sync::Mutex;
 
 pub fn do_logging() {
     let _ = env_logger::builder().is_test(true).try_init();
@@ -14,6 +19,35 @@ pub fn load_data(file: &str) -> String {
     fs::read_to_string(format!("tests/data/reddit/{file}.json")).expect("could not find test data")
 }
 
+/// Loads test data like [`load_data()`], after letting `edit` change it.
+///
+/// `edit` is given the data of each thing in the file: every thing in a
+/// listing, or the one thing in any other response. Things can be changed,
+/// added, or removed.
+pub fn load_data_with(file: &str, edit: impl FnOnce(&mut Vec<Value>)) -> String {
+    let mut data: Value = serde_json::from_str(&load_data(file)).expect("test data should be JSON");
+    match data["data"]["children"].as_array_mut() {
+        Some(children) => {
+            let kind = children.first().map(|child| child["kind"].clone());
+            let mut things: Vec<Value> = children
+                .iter_mut()
+                .map(|child| child["data"].take())
+                .collect();
+            edit(&mut things);
+            *children = things
+                .into_iter()
+                .map(|thing| json!({"kind": kind, "data": thing}))
+                .collect();
+        }
+        None => {
+            let mut things = vec![data["data"].take()];
+            edit(&mut things);
+            data["data"] = things.pop().expect("test data should have a thing");
+        }
+    }
+    data.to_string()
+}
+
 pub fn load_output(filename: &str) -> String {
     let filename = format!("tests/output/{filename}.txt");
     String::from(
@@ -23,13 +57,91 @@ pub fn load_output(filename: &str) -> String {
     )
 }
 
+/// A service that serves test data from `tests/data/reddit`.
+///
+/// Listings are served as a series of distinct pages: the first page is
+/// the test data itself, and each later page is a copy of it with the
+/// things' IDs changed, so that things on different pages are different.
+/// The cursor requested for each page is recorded.
 pub struct TestService<'a> {
     suffix: &'a str,
+    pages: Option<usize>,
+    cursors: Mutex<Vec<(&'static str, Option<String>)>>,
 }
 
 impl<'a> TestService<'a> {
     pub fn new(suffix: &'a str) -> Self {
-        Self { suffix }
+        Self {
+            suffix,
+            pages: None,
+            cursors: Mutex::new(vec![]),
+        }
+    }
+
+    /// Serves only `pages` pages of each listing, instead of as many as
+    /// are requested.
+    pub fn pages(self, pages: usize) -> Self {
+        Self {
+            pages: Some(pages),
+            ..self
+        }
+    }
+
+    /// The cursors that were requested for each page of the `resource`
+    /// listing, in order.
+    pub fn cursors(&self, resource: &str) -> Vec<Option<String>> {
+        self.cursors
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| *name == resource)
+            .map(|(_, cursor)| cursor.clone())
+            .collect()
+    }
+
+    /// The page of a listing following `cursor`, made from the listing's
+    /// test `data`.
+    fn page(&self, resource: &'static str, cursor: Option<&str>, data: String) -> String {
+        self.cursors
+            .lock()
+            .unwrap()
+            .push((resource, cursor.map(String::from)));
+
+        let mut page: Value = serde_json::from_str(&data).expect("test data should be JSON");
+        let Some(after) = page["data"]["after"].as_str().map(String::from) else {
+            assert!(cursor.is_none(), "unexpected cursor {cursor:?}");
+            return data;
+        };
+        let index = match cursor {
+            None => 0,
+            Some(cursor) if cursor == after => 1,
+            Some(cursor) => cursor
+                .strip_prefix(&format!("{after}_"))
+                .and_then(|index| index.parse().ok())
+                .unwrap_or_else(|| panic!("unexpected cursor {cursor}")),
+        };
+        let is_last = self.pages.is_some_and(|pages| index + 1 >= pages);
+        if index == 0 && !is_last {
+            return data;
+        }
+
+        if index > 0
+            && let Some(children) = page["data"]["children"].as_array_mut()
+        {
+            for child in children {
+                for key in ["id", "name"] {
+                    if let Some(value) = child["data"][key].as_str() {
+                        child["data"][key] = Value::from(format!("{value}_{index}"));
+                    }
+                }
+            }
+        }
+        page["data"]["after"] = if is_last {
+            Value::Null
+        } else {
+            Value::from(format!("{after}_{}", index + 1))
+        };
+        page.to_string()
     }
 }
 
@@ -43,19 +155,44 @@ impl<'a> HttpGet for TestService<'a> {
 }
 
 impl<'a> Service for TestService<'a> {
-    async fn get_resource(&self, _username: &str, resource: &str) -> HttpResult<String> {
-        let filename = format!("tests/data/reddit/{resource}_{}.json", self.suffix);
-        self.get(&filename).await
+    async fn get_resource(&self, _username: &str, resource: &Resource) -> HttpResult<String> {
+        let filename = format!("tests/data/reddit/{}_{}.json", resource.name(), self.suffix);
+        let data = self.get(&filename).await?;
+        match resource.params() {
+            Some(params) => Ok(self.page(resource.name(), params.cursor(), data)),
+            None => Ok(data),
+        }
+    }
+}
+
+impl User {
+    /// Returns a user with 100 submissions and 100 comments that can be
+    /// used for testing purposes.
+    pub fn test() -> User {
+        User::parse(
+            load_data("about_mipadi"),
+            load_data("comments_mipadi"),
+            load_data("submitted_mipadi"),
+        )
+        .unwrap()
     }
 }
 
+fn test_options() -> ListingOptions {
+    ListingOptions::default().progress(false)
+}
+
 impl Redditor {
     /// Returns a valid Redditor with 100 submissions and 100 comments
     /// that can be used for testing purposes.
     pub async fn test() -> Redditor {
-        Redditor::with_service(String::from("mipadi"), TestService::new("mipadi"))
-            .await
-            .unwrap()
+        Redditor::with_service(
+            String::from("mipadi"),
+            &TestService::new("mipadi"),
+            &test_options(),
+        )
+        .await
+        .unwrap()
     }
 
     /// Returns a valid Redditor with no submissions nor comments that can
@@ -63,7 +200,8 @@ impl Redditor {
     pub async fn test_empty() -> Redditor {
         Redditor::with_service(
             String::from("testuserpleaseignore"),
-            TestService::new("empty"),
+            &TestService::new("empty"),
+            &test_options(),
         )
         .await
         .unwrap()
@@ -71,8 +209,12 @@ impl Redditor {
 
     /// Returns a non-existent Redditor.
     pub async fn test_none() -> Option<Redditor> {
-        Redditor::with_service(String::from("doesnotexist"), TestService::new("404"))
-            .await
-            .ok()
+        Redditor::with_service(
+            String::from("doesnotexist"),
+            &TestService::new("404"),
+            &test_options(),
+        )
+        .await
+        .ok()
     }
 }
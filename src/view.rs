@@ -5,11 +5,15 @@
 
 use crate::cli::DateFormat;
 use crate::count::SubredditCount;
-use crate::reddit::client::{Redditor, Timeline};
-use crate::reddit::thing::{Comment, HasBody, HasSubreddit, Submission};
+use crate::diff::{Change, Diff, Diffable, Line};
+use crate::reddit::client::{Redditor, Thread, Timeline};
+use crate::reddit::thing::{
+    Comment, Distinguished, HasBody, HasSubreddit, MediaType, Removable, Submission,
+};
 use crate::text::RegexReplaceable;
+use chrono::Local;
 use colored::Colorize;
-use horologe::{Clock, age::HasAge};
+use horologe::{Clock, DateTime, age::HasAge};
 use indoc::formatdoc;
 use itertools::Itertools;
 
@@ -73,16 +77,20 @@ trait DateFormattable: HasAge {
 
     /// Formats an absolute date associated with the data structure.
     fn format_absolute_date(&self) -> String {
-        let date = self.created_local();
-        let date_part = format!("{}", date.format("%a, %-d %b %Y"));
-        let time_part = format!("{}", date.format("%l:%M %p"));
-        // %l formats a single-digit time as, e.g., " 8",
-        // but I want to trim off the leading space.
-        let time_part = time_part.trim();
-        format!("{date_part}, {time_part}")
+        format_absolute_date(self.created_local())
     }
 }
 
+/// Formats `date` as an absolute date, like "Thu, 17 Apr 2025, 8:44 PM".
+fn format_absolute_date(date: DateTime<Local>) -> String {
+    let date_part = format!("{}", date.format("%a, %-d %b %Y"));
+    let time_part = format!("{}", date.format("%l:%M %p"));
+    // %l formats a single-digit time as, e.g., " 8",
+    // but I want to trim off the leading space.
+    let time_part = time_part.trim();
+    format!("{date_part}, {time_part}")
+}
+
 /// Marks an item that can be converted into a string for display on a terminal.
 pub trait Viewable {
     /// Converts the item into a string for display on a terminal.
@@ -96,18 +104,55 @@ pub trait Viewable {
 
 impl Viewable for Redditor {
     fn view<C: Clock>(&self, _: &ViewOptions, clock: &C) -> String {
-        formatdoc! {"
+        let about = self.about();
+        let mut info = formatdoc! {"
             Created: {} ({})
             Link Karma: {}
-            Comment Karma: {}",
+            Comment Karma: {}
+            Total Karma: {}
+            Award Karma: {} received, {} given
+            Verified: {}
+            Verified Email: {}
+            Moderator: {}
+            Premium: {}
+            Reddit Employee: {}",
             self.created_local().format("%b %d, %Y %H:%M %p"),
             self.relative_age(clock),
             self.link_karma(),
             self.comment_karma(),
-        }
+            about.total_karma(),
+            about.awardee_karma(),
+            about.awarder_karma(),
+            yes_or_no(about.is_verified()),
+            yes_or_no(about.has_verified_email()),
+            yes_or_no(about.is_mod()),
+            yes_or_no(about.has_premium()),
+            yes_or_no(about.is_employee()),
+        };
+        if about.is_nsfw() {
+            info.push_str("\nNSFW Profile: yes");
+        }
+        if let Some(url) = about.icon_url() {
+            info.push_str(&format!("\nAvatar: {url}"));
+        }
+        let profile = about
+            .profile_title()
+            .map(|title| title.bold().to_string())
+            .into_iter()
+            .chain(about.description().map(String::from))
+            .join("\n");
+        if !profile.is_empty() {
+            info.push_str(&format!("\n\n{profile}"));
+        }
+        info
     }
 }
 
+/// Formats a flag as "yes" or "no".
+fn yes_or_no(flag: bool) -> &'static str {
+    if flag { "yes" } else { "no" }
+}
+
 impl Viewable for Comment {
     fn view<C: Clock>(&self, opts: &ViewOptions, clock: &C) -> String {
         if opts.oneline {
@@ -139,7 +184,7 @@ impl Comment {
             {}
             {}
             {}
-            {} {} {}
+            {} {} {}{}
 
             {body}",
             self.subreddit().green(),
@@ -148,6 +193,7 @@ impl Comment {
             age.blue(),
             "\u{2022}".cyan(),
             format!("{:+}", self.score()).blue(),
+            self.badges(),
         }
     }
 
@@ -155,6 +201,70 @@ impl Comment {
         format!("{} {}", self.subreddit().green(), self.link_title())
     }
 
+    fn view_ancestor<C: Clock>(&self, opts: &ViewOptions, clock: &C) -> String {
+        let age = self.format_date(opts, clock);
+
+        let body = if opts.raw {
+            self.raw_body()
+        } else {
+            self.body()
+        };
+
+        let ancestor = formatdoc! {"
+            {}
+            {}
+            {}
+            {} {} {}{}
+
+            {body}",
+            self.subreddit().green(),
+            self.permalink().yellow(),
+            format!("u/{}", self.author()).magenta(),
+            age.blue(),
+            "\u{2022}".cyan(),
+            format!("{:+}", self.score()).blue(),
+            self.badges(),
+        };
+
+        ancestor
+            .trim_end()
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("    {line}")
+                }
+            })
+            .join("\n")
+    }
+
+    /// Labels for whether the comment was edited, posted by the author of
+    /// its post, distinguished, or posted in an NSFW post, each preceded
+    /// by a bullet.
+    fn badges(&self) -> String {
+        let mut badges = vec![];
+        if self.is_edited() {
+            badges.push("edited".yellow());
+        }
+        if self.is_submitter() {
+            badges.push("OP".cyan());
+        }
+        match self.distinguished() {
+            Some(Distinguished::Moderator) => badges.push("mod".green()),
+            Some(Distinguished::Admin) => badges.push("admin".red()),
+            Some(Distinguished::Special) => badges.push("special".magenta()),
+            None => {}
+        }
+        if self.is_nsfw() {
+            badges.push("NSFW".red());
+        }
+        badges
+            .iter()
+            .map(|badge| format!(" {} {badge}", "\u{2022}".cyan()))
+            .join("")
+    }
+
     fn grep<'a>(&self, opts: &'a ViewOptions) -> Option<&'a str> {
         if opts.raw { None } else { opts.grep.as_deref() }
     }
@@ -192,7 +302,7 @@ impl Submission {
                 self.subreddit().green(),
                 self.short_permalink().yellow(),
                 self.title().magenta(),
-                age.blue(),
+                self.details(age),
                 body,
             }
             // Remove trailing space since the link will be blank for self posts
@@ -204,6 +314,55 @@ impl Submission {
         format!("{} {}", self.subreddit().green(), self.title())
     }
 
+    /// The submission's age, badges like "[NSFW]", upvote ratio, and number
+    /// of comments, separated by bullets.
+    fn details(&self, age: String) -> String {
+        let mut stats = self.badges();
+        if let Some(ratio) = self.upvote_ratio() {
+            stats.push(format!("{:.0}% upvoted", ratio * 100.0).blue().to_string());
+        }
+
+        let comments = match self.num_comments() {
+            1 => String::from("1 comment"),
+            n => format!("{n} comments"),
+        };
+
+        let mut details = vec![age.blue().to_string()];
+        if !stats.is_empty() {
+            details.push(stats.join(" "));
+        }
+        details.push(comments.blue().to_string());
+        details.join(&format!(" {} ", "\u{2022}".cyan()))
+    }
+
+    /// Bracketed labels for the submission's flair and anything notable
+    /// about it, like whether it is NSFW or has been removed.
+    fn badges(&self) -> Vec<String> {
+        let mut badges = vec![];
+        if let Some(flair) = self.flair() {
+            badges.push(format!("[{flair}]").green());
+        }
+        match self.media_type() {
+            MediaType::Image => badges.push("[Image]".cyan()),
+            MediaType::Video => badges.push("[Video]".cyan()),
+            MediaType::Embed => badges.push("[Embed]".cyan()),
+            MediaType::Text | MediaType::Link => {}
+        }
+        if self.is_nsfw() {
+            badges.push("[NSFW]".red());
+        }
+        if self.is_spoiler() {
+            badges.push("[Spoiler]".yellow());
+        }
+        if self.is_locked() {
+            badges.push("[Locked]".yellow());
+        }
+        if self.is_removed() {
+            badges.push("[Removed]".red());
+        }
+        badges.iter().map(ToString::to_string).collect()
+    }
+
     fn short_permalink(&self) -> String {
         let permalink = self.permalink();
         let parts: Vec<_> = permalink.split("/").collect();
@@ -216,6 +375,79 @@ impl Submission {
     }
 }
 
+impl Viewable for Diff<'_> {
+    fn view<C: Clock>(&self, opts: &ViewOptions, clock: &C) -> String {
+        self.comments()
+            .map(|change| change.view(opts, clock))
+            .chain(self.submissions().map(|change| change.view(opts, clock)))
+            .join("\n\n\n")
+    }
+}
+
+impl<T: Diffable> Viewable for Change<'_, T> {
+    fn view<C: Clock>(&self, opts: &ViewOptions, clock: &C) -> String {
+        let (thing, status, body) = match self {
+            Change::Removed(thing) => (thing, "removed".red().to_string(), thing.raw_body()),
+            Change::Edited { after, .. } => {
+                let edited = match after.edited() {
+                    Some(edited) => format!("edited {}", format_absolute_date(edited.into())),
+                    None => String::from("edited"),
+                };
+                let body = self
+                    .lines()
+                    .into_iter()
+                    .map(|line| match line {
+                        Line::Unchanged(line) => format!("  {line}"),
+                        Line::Deleted(line) => format!("- {line}").red().to_string(),
+                        Line::Inserted(line) => format!("+ {line}").green().to_string(),
+                    })
+                    .join("\n");
+                (after, edited.yellow().to_string(), body)
+            }
+            Change::Rescored { before, after } => {
+                let score = format!(
+                    "score {:+} \u{2192} {:+} ({:+})",
+                    before.score(),
+                    after.score(),
+                    after.score() - before.score()
+                );
+                (after, score.cyan().to_string(), String::new())
+            }
+        };
+        let age = match opts.date_format {
+            DateFormat::Relative => thing.relative_age(clock),
+            DateFormat::Absolute => format_absolute_date(thing.created_local()),
+        };
+
+        String::from(
+            formatdoc! {"
+                {}
+                {}
+                {}
+                {} {} {}
+
+                {body}",
+                thing.subreddit().green(),
+                thing.permalink().yellow(),
+                thing.title().magenta(),
+                age.blue(),
+                "\u{2022}".cyan(),
+                status,
+            }
+            .trim_end(),
+        )
+    }
+}
+
+impl Viewable for Thread<'_> {
+    fn view<C: Clock>(&self, opts: &ViewOptions, clock: &C) -> String {
+        self.ancestors()
+            .map(|ancestor| ancestor.view_ancestor(opts, clock))
+            .chain(std::iter::once(self.comment().view(opts, clock)))
+            .join("\n\n")
+    }
+}
+
 impl Viewable for Vec<SubredditCount> {
     fn view<C: Clock>(&self, _: &ViewOptions, _: &C) -> String {
         let width = self
@@ -398,6 +630,25 @@ mod tests {
             assert_eq!(actual, expected, "\nleft:\n{actual}\n\nright:\n{expected}");
         }
 
+        #[tokio::test]
+        async fn it_formats_an_edited_comment() {
+            let opts = ViewOptions::default().date_format(DateFormat::Absolute);
+            let actual = get_comment(33).await.view(&opts, &FrozenClock::default());
+            let details = actual.lines().nth(3).unwrap();
+            assert!(
+                details.ends_with(&"edited".yellow().to_string()),
+                "{details}"
+            );
+        }
+
+        #[tokio::test]
+        async fn it_does_not_flag_an_unedited_comment() {
+            let opts = ViewOptions::default().date_format(DateFormat::Absolute);
+            let actual = get_comment(0).await.view(&opts, &FrozenClock::default());
+            let details = actual.lines().nth(3).unwrap();
+            assert!(!details.contains("edited"), "{details}");
+        }
+
         #[tokio::test]
         async fn it_formats_a_comment_on_oneline() {
             let opts = ViewOptions::default().oneline(true);
@@ -499,6 +750,33 @@ mod tests {
             assert_eq!(actual, expected);
         }
 
+        #[tokio::test]
+        async fn it_formats_the_details_of_a_post() {
+            let post = get_post(0).await;
+            let expected = format!(
+                "{} {} {} {} {} {}",
+                "4 months ago".blue(),
+                "\u{2022}".cyan(),
+                "[Discussion]".green(),
+                "83% upvoted".blue(),
+                "\u{2022}".cyan(),
+                "22 comments".blue(),
+            );
+            assert_eq!(post.details(String::from("4 months ago")), expected);
+        }
+
+        #[tokio::test]
+        async fn it_formats_the_badges_of_a_removed_post() {
+            let post = get_post(13).await;
+            assert_eq!(
+                post.badges(),
+                [
+                    "[Already Submitted]".green().to_string(),
+                    "[Removed]".red().to_string()
+                ]
+            );
+        }
+
         #[tokio::test]
         async fn it_formats_a_post_on_oneline() {
             let opts = ViewOptions::default().oneline(true);
@@ -605,4 +883,96 @@ mod tests {
             assert_eq!(actual, expected);
         }
     }
+
+    mod format_diff {
+        use super::super::*;
+        use super::with_no_color;
+        use crate::test_utils::load_data_with;
+        use horologe::testing::FrozenClock;
+        use serde_json::Value;
+
+        fn comments(edit: impl FnOnce(&mut Value)) -> Vec<Comment> {
+            let data = load_data_with("comments_mipadi", |comments| edit(&mut comments[0]));
+            Comment::parse_page(&data).unwrap().into_items()
+        }
+
+        #[test]
+        fn it_formats_a_score_change() {
+            let before = comments(|_| {});
+            let after = comments(|comment| comment["score"] = Value::from(5));
+            let change = Change::Rescored {
+                before: &before[0],
+                after: &after[0],
+            };
+            let actual =
+                with_no_color(|| change.view(&ViewOptions::default(), &FrozenClock::default()));
+            assert!(
+                actual.starts_with(
+                    "cyphersystem\n\
+                    https://www.reddit.com/r/cyphersystem/comments/1k1iixf/z/mnpd3zh\n\
+                    Cypher System & ChatGPT\n"
+                ),
+                "{actual}"
+            );
+            assert!(
+                actual.ends_with("\u{2022} score +1 \u{2192} +5 (+4)"),
+                "{actual}"
+            );
+        }
+
+        #[test]
+        fn it_formats_an_edit() {
+            let before = comments(|comment| comment["body"] = Value::from("Hello\nworld"));
+            let after = comments(|comment| comment["body"] = Value::from("Hello\nthere"));
+            let change = Change::Edited {
+                before: &before[0],
+                after: &after[0],
+            };
+            let actual =
+                with_no_color(|| change.view(&ViewOptions::default(), &FrozenClock::default()));
+            assert!(
+                actual.ends_with("\u{2022} edited\n\n  Hello\n- world\n+ there"),
+                "{actual}"
+            );
+        }
+    }
+
+    mod format_thread {
+        use super::super::*;
+        use super::with_no_color;
+        use crate::reddit::client::ListingOptions;
+        use crate::test_utils::TestService;
+        use horologe::testing::FrozenClock;
+
+        #[tokio::test]
+        async fn it_formats_ancestors_above_the_comment() {
+            let options = ListingOptions::default().progress(false).context(2);
+            let user = Redditor::with_service("mipadi", &TestService::new("mipadi"), &options)
+                .await
+                .unwrap();
+            let comment = user.comments().nth(3).unwrap();
+            let opts = ViewOptions::default().raw(true);
+            let actual =
+                with_no_color(|| user.thread(comment).view(&opts, &FrozenClock::default()));
+            let starter = actual.find("    u/ThreadStarter\n").expect(&actual);
+            let replier = actual.find("    u/ThreadReplier\n").expect(&actual);
+            let reply = actual
+                .find(&format!("\n{}\n", comment.link_title()))
+                .expect(&actual);
+            assert!(starter < replier && replier < reply, "{actual}");
+            assert!(actual.starts_with("    rpg\n"), "{actual}");
+        }
+
+        #[tokio::test]
+        async fn it_formats_a_comment_without_context() {
+            let user = Redditor::test().await;
+            let comment = user.comments().nth(3).unwrap();
+            let opts = ViewOptions::default().date_format(DateFormat::Absolute);
+            let clock = FrozenClock::default();
+            assert_eq!(
+                user.thread(comment).view(&opts, &clock),
+                comment.view(&opts, &clock)
+            );
+        }
+    }
 }
@@ -6,6 +6,7 @@
 
 use hypertyper::HttpError;
 use reqwest::StatusCode;
+use usaidwat::reddit::resource::{ListingParams, Resource};
 use usaidwat::reddit::service::{RedditService, Service};
 use uuid::Uuid;
 
@@ -13,7 +14,10 @@ use uuid::Uuid;
 #[ignore = "reddit now requires a logged-in user or a cookie or something"]
 async fn it_retrieves_profiles() {
     let service = RedditService::default();
-    let resp = service.get_resource("mipadi", "about").await.unwrap();
+    let resp = service
+        .get_resource("mipadi", &Resource::About)
+        .await
+        .unwrap();
     assert_ne!(resp, "");
 }
 
@@ -21,7 +25,10 @@ async fn it_retrieves_profiles() {
 #[ignore = "reddit now requires a logged-in user or a cookie or something"]
 async fn it_retrieves_comments() {
     let service = RedditService::default();
-    let resp = service.get_resource("mipadi", "comments").await.unwrap();
+    let resp = service
+        .get_resource("mipadi", &Resource::Comments(ListingParams::default()))
+        .await
+        .unwrap();
     assert_ne!(resp, "");
 }
 
@@ -29,7 +36,10 @@ async fn it_retrieves_comments() {
 #[ignore = "reddit now requires a logged-in user or a cookie or something"]
 async fn it_retrieves_posts() {
     let service = RedditService::default();
-    let resp = service.get_resource("mipadi", "submitted").await.unwrap();
+    let resp = service
+        .get_resource("mipadi", &Resource::Submitted(ListingParams::default()))
+        .await
+        .unwrap();
     assert_ne!(resp, "");
 }
 
@@ -38,7 +48,7 @@ async fn it_retrieves_posts() {
 async fn it_returns_an_error_for_invalid_users() {
     let service = RedditService::default();
     let user = Uuid::new_v4().to_string();
-    let resp = service.get_resource(&user, "about").await;
+    let resp = service.get_resource(&user, &Resource::About).await;
     assert!(resp.is_err(), "response was {resp:?}");
 
     if let Err(HttpError::Request(http_error)) = &resp
@@ -0,0 +1,274 @@
+// These tests exercise the real RedditService code path against a local
+// stand-in for the Reddit API, so unlike the tests in reddit_service_https.rs,
+// they don't need network access or Reddit credentials.
+
+use hypertyper::HttpError;
+use reqwest::StatusCode;
+use std::fs;
+use usaidwat::reddit::auth::Credentials;
+use usaidwat::reddit::cassette::CassetteService;
+use usaidwat::reddit::resource::{ListingParams, Resource};
+use usaidwat::reddit::service::{RedditService, Service};
+use wiremock::matchers::{body_string_contains, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn load_data(file: &str) -> String {
+    fs::read_to_string(format!("tests/data/reddit/{file}.json")).expect("could not find test data")
+}
+
+fn json_response(file: &str) -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(load_data(file), "application/json; charset=UTF-8")
+}
+
+fn token_response() -> ResponseTemplate {
+    ResponseTemplate::new(200).set_body_raw(
+        r#"{"access_token": "abc123", "token_type": "bearer", "expires_in": 86400, "scope": "*"}"#,
+        "application/json",
+    )
+}
+
+fn service(server: &MockServer) -> RedditService {
+    RedditService::new(None).base_uri(server.uri())
+}
+
+fn authenticated_service(server: &MockServer) -> RedditService {
+    RedditService::new(Some(Credentials::new("id", "secret")))
+        .base_uri(server.uri())
+        .auth_uri(server.uri())
+}
+
+#[tokio::test]
+async fn it_retrieves_profiles() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(json_response("about_mipadi"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource("mipadi", &Resource::About)
+        .await
+        .unwrap();
+    assert_eq!(resp, load_data("about_mipadi"));
+}
+
+#[tokio::test]
+async fn it_retrieves_comments() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/comments.json"))
+        .and(query_param("limit", "100"))
+        .respond_with(json_response("comments_mipadi"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource("mipadi", &Resource::Comments(ListingParams::default()))
+        .await
+        .unwrap();
+    assert_eq!(resp, load_data("comments_mipadi"));
+}
+
+#[tokio::test]
+async fn it_retrieves_the_next_page_of_posts() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/submitted.json"))
+        .and(query_param("after", "t3_2au1ki"))
+        .respond_with(json_response("submitted_empty"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource(
+            "mipadi",
+            &Resource::Submitted(ListingParams::default().after("t3_2au1ki")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp, load_data("submitted_empty"));
+}
+
+#[tokio::test]
+async fn it_returns_an_error_for_invalid_users() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/doesnotexist/about.json"))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_raw(load_data("about_404"), "application/json"),
+        )
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource("doesnotexist", &Resource::About)
+        .await;
+    assert!(
+        matches!(resp, Err(HttpError::Http(StatusCode::NOT_FOUND))),
+        "response was {resp:?}"
+    );
+}
+
+#[tokio::test]
+async fn it_returns_an_error_for_unexpected_content_types() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html"))
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource("mipadi", &Resource::About)
+        .await;
+    assert!(
+        matches!(&resp, Err(HttpError::UnexpectedContentType(content_type)) if content_type == "text/html"),
+        "response was {resp:?}"
+    );
+}
+
+#[tokio::test]
+async fn it_retries_server_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .expect(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(json_response("about_mipadi"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp = service(&server)
+        .get_resource("mipadi", &Resource::About)
+        .await
+        .unwrap();
+    assert_eq!(resp, load_data("about_mipadi"));
+}
+
+#[tokio::test]
+async fn it_authenticates_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/access_token"))
+        .and(body_string_contains("grant_type=client_credentials"))
+        .respond_with(token_response())
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .and(header("authorization", "Bearer abc123"))
+        .respond_with(json_response("about_mipadi"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let service = authenticated_service(&server);
+    for _ in 0..2 {
+        let resp = service
+            .get_resource("mipadi", &Resource::About)
+            .await
+            .unwrap();
+        assert_eq!(resp, load_data("about_mipadi"));
+    }
+}
+
+#[tokio::test]
+async fn it_returns_an_error_if_authentication_fails() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/access_token"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let resp = authenticated_service(&server)
+        .get_resource("mipadi", &Resource::About)
+        .await;
+    assert!(
+        matches!(resp, Err(HttpError::Http(StatusCode::UNAUTHORIZED))),
+        "response was {resp:?}"
+    );
+}
+
+#[tokio::test]
+async fn it_requests_a_new_token_only_once() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/api/v1/access_token"))
+        .respond_with(token_response())
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(ResponseTemplate::new(401))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+
+    let resp = authenticated_service(&server)
+        .get_resource("mipadi", &Resource::About)
+        .await;
+    assert!(
+        matches!(resp, Err(HttpError::Http(StatusCode::UNAUTHORIZED))),
+        "response was {resp:?}"
+    );
+}
+
+#[tokio::test]
+async fn it_records_and_replays_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/user/mipadi/about.json"))
+        .respond_with(json_response("about_mipadi").insert_header("set-cookie", "session=secret"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let cassette = std::env::temp_dir().join(format!("usaidwat-{}.json", uuid::Uuid::new_v4()));
+
+    let recorder = CassetteService::record(service(&server), &cassette);
+    let recorded = recorder
+        .get_resource("mipadi", &Resource::About)
+        .await
+        .unwrap();
+    drop(recorder);
+
+    let player = CassetteService::replay(&cassette).unwrap();
+    let replayed = player
+        .get_resource("mipadi", &Resource::About)
+        .await
+        .unwrap();
+
+    assert_eq!(recorded, load_data("about_mipadi"));
+    assert_eq!(replayed, recorded);
+    let saved = fs::read_to_string(&cassette).unwrap();
+    assert!(saved.contains("content-type"), "{saved}");
+    assert!(!saved.contains("set-cookie"), "{saved}");
+    fs::remove_file(cassette).unwrap();
+}